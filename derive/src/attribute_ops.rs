@@ -7,11 +7,13 @@
 use darling::ast::Data;
 use darling::util::{self, SpannedValue, WithOriginal};
 use darling::{FromAttributes, FromDeriveInput, FromField, FromMeta};
+use itertools::Itertools;
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned;
-use syn::{LitStr, Meta, Type};
+use syn::{Ident, LitStr, Meta, Type};
 
+use crate::rust_to_variant_type;
 use crate::type_paths::godot_types;
 
 #[derive(FromAttributes, Debug)]
@@ -20,17 +22,26 @@ pub struct FieldExportOps {
     color_no_alpha: Option<WithOriginal<bool, Meta>>,
     dir: Option<WithOriginal<bool, Meta>>,
     exp_easing: Option<WithOriginal<syn::ExprArray, Meta>>,
-    file: Option<WithOriginal<syn::ExprArray, Meta>>,
+    file: Option<WithOriginal<ExportFileFilters, Meta>>,
+    extensions: Option<WithOriginal<syn::ExprArray, Meta>>,
     enum_options: Option<WithOriginal<syn::ExprArray, Meta>>,
     flags: Option<WithOriginal<syn::ExprArray, Meta>>,
     global_dir: Option<WithOriginal<bool, Meta>>,
-    global_file: Option<WithOriginal<(), Meta>>,
+    global_file: Option<WithOriginal<ExportFileFilters, Meta>>,
     multiline: Option<WithOriginal<(), Meta>>,
     node_path: Option<WithOriginal<syn::ExprArray, Meta>>,
     placeholder: Option<WithOriginal<String, Meta>>,
     range: Option<WithOriginal<ExportRangeOps, Meta>>,
+    script: Option<WithOriginal<syn::Path, Meta>>,
+    /// Class the root node of an exported `Gd<PackedScene>` has to inherit, either an engine class or a rust script
+    /// class. The resource picker of the inspector can not be scoped to a root node type, so every packed scene can be
+    /// selected. Assigning a scene with another root node only logs a warning at runtime.
+    scene_root: Option<WithOriginal<String, Meta>>,
     #[darling(rename = "ty")]
-    custom_type: Option<WithOriginal<LitStr, Meta>>,
+    custom_type: Option<WithOriginal<ExportTypeList, Meta>>,
+    duplicate: Option<ExportDuplicateOps>,
+    #[darling(default)]
+    readonly: bool,
 }
 
 impl FieldExportOps {
@@ -39,12 +50,16 @@ impl FieldExportOps {
         let property_hints = quote!(#godot_types::global::PropertyHint);
 
         let mut result: Option<(&str, TokenStream, TokenStream)> = None;
+        let extension_filters = self.extension_filters()?;
+
+        // `color_no_alpha = false` keeps the default color picker.
+        if let Some(color_no_alpha) = self.color_no_alpha.as_ref().filter(|opt| opt.parsed) {
+            let hint = quote_spanned!(color_no_alpha.original.span() => Some(#property_hints::COLOR_NO_ALPHA));
 
-        if let Some(color_no_alpha) = self.color_no_alpha.as_ref() {
             result = Some((
                 "color_no_alpha",
-                quote_spanned!(color_no_alpha.original.span() => #property_hints::COLOR_NO_ALPHA),
-                quote_spanned!(color_no_alpha.original.span() => String::new()),
+                Self::require_type(ty, "ColorNoAlphaExport", hint),
+                quote_spanned!(color_no_alpha.original.span() => Some(String::new())),
             ));
         }
 
@@ -88,7 +103,11 @@ impl FieldExportOps {
 
             result = Some((
                 field,
-                quote_spanned!(exp_list.original.span() => Some(#property_hints::EXP_EASING)),
+                Self::require_type(
+                    ty,
+                    "ExpEasingExport",
+                    quote_spanned!(exp_list.original.span() => Some(#property_hints::EXP_EASING)),
+                ),
                 quote_spanned!(exp_list.original.span() => Some(String::from(#serialized_params))),
             ));
         }
@@ -100,14 +119,7 @@ impl FieldExportOps {
                 return Self::error(list.original.span(), active_field, field);
             }
 
-            let filters = list
-                .parsed
-                .elems
-                .iter()
-                .map(String::from_expr)
-                .collect::<Result<Vec<_>, _>>()
-                .map_err(|err| err.write_errors())?
-                .join(",");
+            let filters = list.parsed.filters(&extension_filters);
 
             result = Some((
                 field,
@@ -183,10 +195,12 @@ impl FieldExportOps {
                 return Self::error(global_file.original.span(), active_field, field);
             }
 
+            let filters = global_file.parsed.filters(&extension_filters);
+
             result = Some((
                 field,
                 quote_spanned!(global_file.original.span() => Some(#property_hints::GLOBAL_FILE)),
-                quote_spanned!(global_file.original.span() => Some(String::new())),
+                quote_spanned!(global_file.original.span() => Some(String::from(#filters))),
             ));
         }
 
@@ -197,9 +211,12 @@ impl FieldExportOps {
                 return Self::error(multiline.original.span(), active_field, field);
             }
 
+            let hint =
+                quote_spanned!(multiline.original.span() => Some(#property_hints::MULTILINE));
+
             result = Some((
                 field,
-                quote_spanned!(multiline.original.span() => Some(#property_hints::MULTILINE)),
+                Self::require_type(ty, "MultilineExport", hint),
                 quote_spanned!(multiline.original.span() => Some(String::new())),
             ));
         }
@@ -250,13 +267,78 @@ impl FieldExportOps {
                 return Self::error(ops.original.span(), active_field, field);
             }
 
-            let step = ops.parsed.step.unwrap_or(1.0);
-            let hint_string = format!("{},{},{}", ops.parsed.min, ops.parsed.max, step);
+            if ops.parsed.exp && ops.parsed.min <= 0.0 {
+                return Err(syn::Error::new(
+                    ops.original.span(),
+                    "range with exp requires a min greater than zero",
+                )
+                .into_compile_error());
+            }
+
+            let options = ops.parsed.options();
+
+            let hint_string = match ops.parsed.step {
+                Some(step) => {
+                    let hint_string =
+                        format!("{},{},{}{}", ops.parsed.min, ops.parsed.max, step, options);
+
+                    quote_spanned!(ops.original.span() => String::from(#hint_string))
+                }
+
+                // floats without an explicit step get a continuous slider, integers step by 1.
+                None => {
+                    let variant_type = rust_to_variant_type(ty)?;
+                    let float_hint_string =
+                        format!("{},{}{}", ops.parsed.min, ops.parsed.max, options);
+                    let int_hint_string =
+                        format!("{},{},1{}", ops.parsed.min, ops.parsed.max, options);
+
+                    quote_spanned! {ops.original.span() =>
+                        if #variant_type == #godot_types::sys::VariantType::FLOAT {
+                            String::from(#float_hint_string)
+                        } else {
+                            String::from(#int_hint_string)
+                        }
+                    }
+                }
+            };
 
             result = Some((
                 field,
                 quote_spanned!(ops.original.span() => Some(#property_hints::RANGE)),
-                quote_spanned!(ops.original.span() => Some(String::from(#hint_string))),
+                quote_spanned!(ops.original.span() => Some(#hint_string)),
+            ));
+        }
+
+        if let Some(script) = self.script.as_ref() {
+            let field = "script";
+
+            if let Some((active_field, _, _)) = result {
+                return Self::error(script.original.span(), active_field, field);
+            }
+
+            let script_ty = &script.parsed;
+            let script_base_gd = quote_spanned!(script_ty.span() => #godot_types::obj::Gd<<#script_ty as ::godot_rust_script::GodotScript>::Base>);
+
+            result = Some((
+                field,
+                quote_spanned!(script.original.span() => Some(<#script_base_gd as ::godot_rust_script::GodotScriptExport>::hint(None))),
+                quote_spanned!(script.original.span() => Some(String::from(<#script_ty as ::godot_rust_script::GodotScript>::CLASS_NAME))),
+            ));
+        }
+
+        // packed scenes keep their resource type hint, the root node is checked when the scene is assigned.
+        if let Some(scene_root) = self.scene_root.as_ref() {
+            let field = "scene_root";
+
+            if let Some((active_field, _, _)) = result {
+                return Self::error(scene_root.original.span(), active_field, field);
+            }
+
+            result = Some((
+                field,
+                Self::require_type(ty, "SceneRootExport", quote!(None)),
+                quote!(None),
             ));
         }
 
@@ -267,25 +349,99 @@ impl FieldExportOps {
                 return Self::error(attr_ty.original.span(), active_field, field);
             }
 
-            let attr_ty_raw = &attr_ty.parsed;
+            let types = attr_ty.parsed.0.join(",");
 
             let hint = quote_spanned!(ty.span() => None);
-            let hint_string =
-                quote_spanned!(attr_ty.original.span() => Some(String::from(#attr_ty_raw)));
+            let hint_string = quote_spanned!(attr_ty.original.span() => Some(String::from(#types)));
 
             result = Some((field, hint, hint_string));
         }
 
+        if let Some(extensions) = self.extensions.as_ref() {
+            if self.file.is_none() && self.global_file.is_none() {
+                let err = syn::Error::new(
+                    extensions.original.span(),
+                    "extensions can only be used together with file or global_file",
+                )
+                .into_compile_error();
+
+                return Err(err);
+            }
+        }
+
         let (hint, hint_string) = result
             .map(|(_, tokens, hint_string)| (tokens, hint_string))
             .unwrap_or_else(|| (quote!(None), quote!(None)));
 
-        let default_hint = quote_spanned!(ty.span() => <#ty as ::godot_rust_script::GodotScriptExport>::hint(#hint));
+        // object fields have to be able to represent a cleared property. Scene fields with a root class are exempt, the
+        // option explicitly supports bare packed scenes.
+        let checked_hint = if self.scene_root.is_some() {
+            hint.clone()
+        } else {
+            Self::require_type(ty, "ClearableExport", hint.clone())
+        };
+
+        let default_hint = quote_spanned!(ty.span() => <#ty as ::godot_rust_script::GodotScriptExport>::hint(#checked_hint));
         let default_hint_string = quote_spanned!(ty.span() => <#ty as ::godot_rust_script::GodotScriptExport>::hint_string(#hint, #hint_string));
 
         Ok((default_hint, default_hint_string))
     }
 
+    pub fn usage(&self) -> TokenStream {
+        let godot_types = godot_types();
+        let usage_flags = quote!(#godot_types::global::PropertyUsageFlags);
+
+        let duplicate = match self.duplicate {
+            Some(ExportDuplicateOps::Always) => quote!(#usage_flags::ALWAYS_DUPLICATE),
+            Some(ExportDuplicateOps::Never) => quote!(#usage_flags::NEVER_DUPLICATE),
+            None => quote!(#usage_flags::NONE),
+        };
+
+        if self.readonly {
+            return quote!(#duplicate | #usage_flags::READ_ONLY);
+        }
+
+        duplicate
+    }
+
+    /// Class the root node of an exported packed scene has to inherit.
+    pub fn scene_root(&self) -> Option<&str> {
+        self.scene_root
+            .as_ref()
+            .map(|scene_root| scene_root.parsed.as_str())
+    }
+
+    /// Read-only properties are shown in the inspector but can not be assigned.
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// File filters for the `extensions` option, e.g. `png` becomes `*.png`.
+    fn extension_filters(&self) -> Result<Vec<String>, TokenStream> {
+        let Some(extensions) = self.extensions.as_ref() else {
+            return Ok(Vec::new());
+        };
+
+        let extensions = extensions
+            .parsed
+            .elems
+            .iter()
+            .map(String::from_expr)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| err.write_errors())?;
+
+        let filters = extensions
+            .iter()
+            .map(|extension| {
+                let extension = extension.trim_start_matches('*').trim_start_matches('.');
+
+                format!("*.{extension}")
+            })
+            .collect();
+
+        Ok(filters)
+    }
+
     fn error(
         span: Span,
         active_field: &str,
@@ -299,6 +455,22 @@ impl FieldExportOps {
 
         Err(err)
     }
+
+    /// Wraps the hint of an export option into an assertion that the field type implements the marker trait of the
+    /// option. Checking the trait instead of the type name also accepts type aliases of the supported types.
+    fn require_type(ty: &Type, bound: &str, hint: TokenStream) -> TokenStream {
+        let bound = Ident::new(bound, ty.span());
+
+        quote_spanned! {ty.span()=>
+            {
+                fn require_type<T: ::godot_rust_script::private_export::#bound>() {}
+
+                require_type::<#ty>();
+
+                #hint
+            }
+        }
+    }
 }
 
 #[derive(FromMeta, Debug)]
@@ -306,6 +478,34 @@ struct ExportRangeOps {
     min: f64,
     max: f64,
     step: Option<f64>,
+    #[darling(default)]
+    or_greater: bool,
+    #[darling(default)]
+    or_less: bool,
+    #[darling(default)]
+    hide_slider: bool,
+    #[darling(default)]
+    exp: bool,
+    suffix: Option<String>,
+}
+
+impl ExportRangeOps {
+    /// Additional range options, each prefixed with a comma so they can be appended to the hint string.
+    fn options(&self) -> String {
+        let flags = [
+            (self.or_greater, "or_greater"),
+            (self.or_less, "or_less"),
+            (self.hide_slider, "hide_slider"),
+            (self.exp, "exp"),
+        ];
+
+        flags
+            .into_iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, flag)| format!(",{flag}"))
+            .chain(self.suffix.iter().map(|suffix| format!(",suffix:{suffix}")))
+            .collect()
+    }
 }
 
 #[derive(FromMeta, Debug)]
@@ -314,8 +514,76 @@ enum ExpEasingOpts {
     PositiveOnly,
 }
 
+/// Allowed class names of an exported object, either a single string or an array of strings.
+#[derive(Debug)]
+struct ExportTypeList(Vec<String>);
+
+impl FromMeta for ExportTypeList {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Array(array) => array
+                .elems
+                .iter()
+                .map(String::from_expr)
+                .collect::<darling::Result<Vec<_>>>()
+                .map(Self),
+            expr => String::from_expr(expr).map(|ty| Self(vec![ty])),
+        }
+    }
+}
+
+/// Filters of a `file` or `global_file` export. Both can be used as a plain flag or with a list of filters.
+#[derive(Debug, Default)]
+struct ExportFileFilters(Vec<String>);
+
+impl ExportFileFilters {
+    /// The hint string of the export, the explicit filters followed by the filters for the `extensions` option.
+    fn filters(&self, extension_filters: &[String]) -> String {
+        self.0.iter().chain(extension_filters).join(",")
+    }
+}
+
+impl FromMeta for ExportFileFilters {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::default())
+    }
+
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let syn::Expr::Array(array) = expr else {
+            return Err(darling::Error::unexpected_expr_type(expr));
+        };
+
+        array
+            .elems
+            .iter()
+            .map(String::from_expr)
+            .collect::<darling::Result<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+#[derive(Debug)]
+enum ExportDuplicateOps {
+    Always,
+    Never,
+}
+
+impl FromMeta for ExportDuplicateOps {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        from_ident_expr(expr)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
 #[derive(FromField, Debug)]
-#[darling(forward_attrs(export, prop, doc, signal))]
+#[darling(forward_attrs(export, export_category, prop, doc, signal))]
 pub struct FieldOpts {
     pub ident: Option<syn::Ident>,
     pub attrs: Vec<syn::Attribute>,
@@ -328,13 +596,121 @@ pub struct FieldOpts {
 pub struct GodotScriptOpts {
     pub ident: syn::Ident,
     pub data: Data<util::Ignored, SpannedValue<FieldOpts>>,
-    pub base: Option<syn::Ident>,
+    pub base: Option<syn::Path>,
+    pub rename: Option<LitStr>,
+    pub prefix: Option<LitStr>,
+    pub icon: Option<LitStr>,
+    /// `fn(&mut Self)` that is called after a property has been changed in the inspector.
+    pub on_validate: Option<syn::Expr>,
+    /// `fn(&Self) -> Option<Vec<PropertyInfo>>` that lists properties in addition to the script fields.
+    pub dynamic_properties: Option<syn::Expr>,
+    /// `fn(&Self, StringName) -> Option<Variant>` that reads properties which are not script fields.
+    pub get_fallback: Option<syn::Expr>,
+    /// `fn(&mut Self, StringName, Variant) -> bool` that writes properties which are not script fields.
+    pub set_fallback: Option<syn::Expr>,
+    /// `fn(&Self) -> PackedStringArray` that reports the configuration warnings of the node.
+    pub configuration_warnings: Option<syn::Expr>,
+    /// `fn(&Self) -> String` that replaces the string representation of the script instance. Without it, the fields
+    /// of the script are printed after `<ClassName#instance_id>`.
+    pub to_string: Option<syn::Expr>,
+    #[darling(default)]
+    pub deprecated: bool,
+    #[darling(default)]
+    pub experimental: bool,
+    #[darling(default)]
+    pub tool: bool,
     pub attrs: Vec<syn::Attribute>,
 }
 
+#[derive(FromAttributes, Debug)]
+#[darling(attributes(signal))]
+pub struct SignalOpts {
+    /// The signal is declared by the base class of the script.
+    #[darling(default)]
+    pub base: bool,
+}
+
+#[derive(FromAttributes, Debug)]
+#[darling(attributes(export_category))]
+pub struct ExportCategoryOpts {
+    pub name: LitStr,
+}
+
 #[derive(FromAttributes, Debug)]
 #[darling(attributes(prop))]
 pub struct PropertyOpts {
     pub get: Option<syn::Expr>,
     pub set: Option<syn::Expr>,
+    pub default: Option<syn::Expr>,
+}
+
+/// Options of the `#[rpc(...)]` attribute on script methods, like the `@rpc` annotation in GDScript.
+#[derive(FromAttributes, Debug)]
+#[darling(attributes(rpc))]
+pub struct RpcOpts {
+    #[darling(default)]
+    pub mode: RpcModeOpts,
+    #[darling(default)]
+    pub transfer: RpcTransferOpts,
+    #[darling(default)]
+    pub call_local: bool,
+    #[darling(default)]
+    pub channel: i32,
+}
+
+#[derive(Debug, Default)]
+pub enum RpcModeOpts {
+    #[default]
+    Authority,
+    AnyPeer,
+}
+
+impl FromMeta for RpcModeOpts {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        from_ident_expr(expr)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "authority" => Ok(Self::Authority),
+            "any_peer" => Ok(Self::AnyPeer),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub enum RpcTransferOpts {
+    #[default]
+    Reliable,
+    Unreliable,
+    UnreliableOrdered,
+}
+
+impl FromMeta for RpcTransferOpts {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        from_ident_expr(expr)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "reliable" => Ok(Self::Reliable),
+            "unreliable" => Ok(Self::Unreliable),
+            "unreliable_ordered" => Ok(Self::UnreliableOrdered),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// Parses options that are given as a plain identifier, e.g. `mode = any_peer`.
+fn from_ident_expr<T: FromMeta>(expr: &syn::Expr) -> darling::Result<T> {
+    let syn::Expr::Path(path) = expr else {
+        return Err(darling::Error::unexpected_expr_type(expr));
+    };
+
+    let Some(ident) = path.path.get_ident() else {
+        return Err(darling::Error::unexpected_expr_type(expr));
+    };
+
+    T::from_string(&ident.to_string()).map_err(|err| err.with_span(ident))
 }
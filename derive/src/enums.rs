@@ -10,11 +10,17 @@ use darling::{
     FromDeriveInput, FromVariant,
 };
 use itertools::Itertools;
-use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
-use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Ident, Meta, Visibility};
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    parse_macro_input, spanned::Spanned, DeriveInput, Expr, ExprLit, ExprUnary, Ident, Lit, Meta,
+    UnOp, Visibility,
+};
 
-use crate::type_paths::{convert_error_ty, godot_types, property_hints};
+use crate::{
+    compile_error,
+    type_paths::{convert_error_ty, godot_types, property_hints},
+};
 
 #[derive(FromDeriveInput)]
 #[darling(supports(enum_unit), attributes(script_enum))]
@@ -22,12 +28,17 @@ struct EnumDeriveInput {
     vis: Visibility,
     ident: Ident,
     export: Option<WithOriginal<(), Meta>>,
+    repr: Option<Ident>,
+    bitflags: Option<WithOriginal<(), Meta>>,
     data: Data<EnumVariant, Ignored>,
 }
 
 #[derive(FromVariant)]
+#[darling(forward_attrs(doc))]
 struct EnumVariant {
     ident: Ident,
+    discriminant: Option<Expr>,
+    attrs: Vec<syn::Attribute>,
 }
 
 pub fn script_enum_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -44,26 +55,70 @@ pub fn script_enum_derive(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     let enum_error_ident = Ident::new(&format!("{}Error", enum_ident), enum_ident.span());
     let enum_visibility = input.vis;
 
+    let repr = match input.repr {
+        Some(repr) if repr == "u8" || repr == "i64" => repr,
+        Some(repr) => {
+            return compile_error("script enums can only be represented as u8 or i64", repr).into()
+        }
+        None => Ident::new("u8", Span::call_site()),
+    };
+
     let variants = input.data.take_enum().unwrap();
 
-    let (from_variants, into_variants, hint_strings): (TokenStream, TokenStream, Vec<_>) = variants
+    let variant_values = match variant_values(&variants, &repr, input.bitflags.is_some()) {
+        Ok(values) => values,
+        Err(err) => return err.into(),
+    };
+
+    let (from_variants, into_variants, hint_strings, variant_descs): (
+        TokenStream,
+        TokenStream,
+        Vec<_>,
+        TokenStream,
+    ) = variants
         .iter()
-        .enumerate()
-        .map(|(index, variant)| {
+        .zip(variant_values)
+        .map(|(variant, value)| {
             let variant_ident = &variant.ident;
-            let index = index as u8;
+            let variant_name = variant_ident.to_string();
+            let variant_description = variant_description(variant);
+            let index = match Literal::u64_unsuffixed(value.unsigned_abs()) {
+                lit if value < 0 => quote!(-#lit),
+                lit => lit.into_token_stream(),
+            };
 
             (
                 quote_spanned! {variant_ident.span()=> #enum_ident::#variant_ident => #index,},
                 quote_spanned! {variant_ident.span()=> #index => Ok(#enum_ident::#variant_ident),},
-                format!("{variant_ident}:{index}"),
+                format!("{variant_ident}:{value}"),
+                quote_spanned! {variant_ident.span()=>
+                    ::godot_rust_script::ScriptEnumVariant {
+                        name: #variant_name,
+                        value: #value,
+                        description: concat!(#variant_description),
+                    },
+                },
             )
         })
         .multiunzip();
     let enum_property_hint_str = hint_strings.join(",");
 
+    let derive_bitflags = input.bitflags.map(|bitflags| {
+        derive_bitflags(
+            &bitflags.original,
+            &enum_ident,
+            &enum_visibility,
+            &enum_error_ident,
+            &repr,
+            &variants,
+            input.export.is_some(),
+        )
+    });
+
     let derive_export = input.export.map(|export| {
         quote_spanned! {export.original.span()=>
+            impl ::godot_rust_script::ClearableExport for #enum_ident {}
+
             impl ::godot_rust_script::GodotScriptExport for #enum_ident {
                 fn hint(custom: Option<#property_hints>) -> #property_hints {
                     if let Some(custom) = custom {
@@ -101,12 +156,16 @@ pub fn script_enum_derive(input: proc_macro::TokenStream) -> proc_macro::TokenSt
         }
 
         impl #godot_types::meta::GodotConvert for #enum_ident {
-            type Via = u8;
+            type Via = #repr;
         }
 
-        impl GodotScriptEnum for #enum_ident {}
+        impl GodotScriptEnum for #enum_ident {
+            const VARIANTS: &'static [::godot_rust_script::ScriptEnumVariant] = &[
+                #variant_descs
+            ];
+        }
 
-        impl From<&#enum_ident> for u8 {
+        impl From<&#enum_ident> for #repr {
             fn from(value: &#enum_ident) -> Self {
                 match value {
                     #from_variants
@@ -115,7 +174,7 @@ pub fn script_enum_derive(input: proc_macro::TokenStream) -> proc_macro::TokenSt
         }
 
         #[derive(Debug)]
-        #enum_visibility struct #enum_error_ident(u8);
+        #enum_visibility struct #enum_error_ident(#repr);
 
         impl ::std::fmt::Display for #enum_error_ident {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -125,10 +184,10 @@ pub fn script_enum_derive(input: proc_macro::TokenStream) -> proc_macro::TokenSt
 
         impl ::std::error::Error for #enum_error_ident {}
 
-        impl TryFrom<u8> for #enum_ident {
+        impl TryFrom<#repr> for #enum_ident {
             type Error = #enum_error_ident;
 
-            fn try_from(value: u8) -> ::std::result::Result<Self, Self::Error> {
+            fn try_from(value: #repr) -> ::std::result::Result<Self, Self::Error> {
                 match value {
                     #into_variants
                     _ => Err(#enum_error_ident(value)),
@@ -137,7 +196,252 @@ pub fn script_enum_derive(input: proc_macro::TokenStream) -> proc_macro::TokenSt
         }
 
         #derive_export
+
+        #derive_bitflags
     };
 
     derived.into()
 }
+
+/// Generates the `{Enum}Flags` set type which holds any combination of the enum variants.
+fn derive_bitflags(
+    original: &Meta,
+    enum_ident: &Ident,
+    enum_visibility: &Visibility,
+    enum_error_ident: &Ident,
+    repr: &Ident,
+    variants: &[EnumVariant],
+    export: bool,
+) -> TokenStream {
+    let godot_types = godot_types();
+    let convert_error = convert_error_ty();
+    let property_hints = property_hints();
+    let flags_ident = Ident::new(&format!("{}Flags", enum_ident), enum_ident.span());
+    let all_bits = variants
+        .iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+
+            quote!(#repr::from(&#enum_ident::#variant_ident))
+        })
+        .reduce(|acc, bits| quote!(#acc | #bits))
+        .unwrap_or_else(|| quote!(0));
+    let flags_hint_str = variants
+        .iter()
+        .map(|variant| variant.ident.to_string())
+        .join(",");
+
+    let derive_export = export.then(|| {
+        quote_spanned! {original.span()=>
+            impl ::godot_rust_script::ClearableExport for #flags_ident {}
+
+            impl ::godot_rust_script::GodotScriptExport for #flags_ident {
+                fn hint(custom: Option<#property_hints>) -> #property_hints {
+                    if let Some(custom) = custom {
+                        return custom;
+                    }
+
+                    #property_hints::FLAGS
+                }
+
+                fn hint_string(_custom_hint: Option<#property_hints>, custom_string: Option<String>) -> String {
+                    if let Some(custom_string) = custom_string {
+                        return custom_string;
+                    }
+
+                    String::from(#flags_hint_str)
+                }
+            }
+        }
+    });
+
+    quote_spanned! {original.span()=>
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+        #enum_visibility struct #flags_ident(#repr);
+
+        impl #flags_ident {
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            pub const fn bits(&self) -> #repr {
+                self.0
+            }
+
+            pub fn contains(&self, flag: #enum_ident) -> bool {
+                let bits = #repr::from(&flag);
+
+                self.0 & bits == bits
+            }
+
+            pub fn insert(&mut self, flag: #enum_ident) {
+                self.0 |= #repr::from(&flag);
+            }
+
+            pub fn remove(&mut self, flag: #enum_ident) {
+                self.0 &= !#repr::from(&flag);
+            }
+        }
+
+        impl From<#enum_ident> for #flags_ident {
+            fn from(value: #enum_ident) -> Self {
+                Self(#repr::from(&value))
+            }
+        }
+
+        impl TryFrom<#repr> for #flags_ident {
+            type Error = #enum_error_ident;
+
+            fn try_from(value: #repr) -> ::std::result::Result<Self, Self::Error> {
+                if value & !(#all_bits) != 0 {
+                    return Err(#enum_error_ident(value));
+                }
+
+                Ok(Self(value))
+            }
+        }
+
+        impl ::std::ops::BitOr for #enum_ident {
+            type Output = #flags_ident;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                #flags_ident::from(self) | rhs
+            }
+        }
+
+        impl ::std::ops::BitOr<#enum_ident> for #flags_ident {
+            type Output = Self;
+
+            fn bitor(self, rhs: #enum_ident) -> Self::Output {
+                Self(self.0 | #repr::from(&rhs))
+            }
+        }
+
+        impl ::std::ops::BitOr for #flags_ident {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                Self(self.0 | rhs.0)
+            }
+        }
+
+        impl ::std::ops::BitAnd<#enum_ident> for #flags_ident {
+            type Output = Self;
+
+            fn bitand(self, rhs: #enum_ident) -> Self::Output {
+                Self(self.0 & #repr::from(&rhs))
+            }
+        }
+
+        impl ::std::ops::BitAnd for #flags_ident {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self::Output {
+                Self(self.0 & rhs.0)
+            }
+        }
+
+        impl #godot_types::meta::GodotConvert for #flags_ident {
+            type Via = #repr;
+        }
+
+        impl #godot_types::meta::FromGodot for #flags_ident {
+            fn try_from_godot(via: Self::Via) -> Result<Self, #convert_error> {
+                <#flags_ident as TryFrom<Self::Via>>::try_from(via)
+                    .map_err(|err| #convert_error::with_error_value(err, via))
+            }
+        }
+
+        impl #godot_types::meta::ToGodot for #flags_ident {
+            type ToVia<'a> = Self::Via;
+
+            fn to_godot(&self) -> Self::Via {
+                self.0
+            }
+        }
+
+        #derive_export
+    }
+}
+
+/// Values of all enum variants. Variants without an explicit discriminant continue counting from the previous variant,
+/// just like rust does. Bitflag variants each occupy the bit of their position.
+fn variant_values(
+    variants: &[EnumVariant],
+    repr: &Ident,
+    bitflags: bool,
+) -> Result<Vec<i64>, TokenStream> {
+    let mut next = 0;
+
+    variants
+        .iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            let value = match &variant.discriminant {
+                Some(expr) if bitflags => {
+                    return Err(compile_error(
+                        "bitflag enum variants can not have a discriminant",
+                        expr,
+                    ))
+                }
+                Some(expr) => discriminant_value(expr)?,
+                None if bitflags => 1i64.checked_shl(index as u32).ok_or_else(|| {
+                    compile_error("too many variants for a bitflag enum", &variant.ident)
+                })?,
+                None => next,
+            };
+
+            if repr == "u8" && !(0..=u8::MAX as i64).contains(&value) {
+                return Err(compile_error(
+                    "script enum values have to be in the range of 0 to 255, use `#[script_enum(repr = i64)]` for larger values",
+                    variant.discriminant.as_ref().map_or_else(
+                        || variant.ident.to_token_stream(),
+                        ToTokens::to_token_stream,
+                    ),
+                ));
+            }
+
+            next = value + 1;
+
+            Ok(value)
+        })
+        .collect()
+}
+
+fn discriminant_value(expr: &Expr) -> Result<i64, TokenStream> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse().map_err(|err| err.into_compile_error()),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).map(|value| -value),
+        Expr::Paren(paren) => discriminant_value(&paren.expr),
+        Expr::Group(group) => discriminant_value(&group.expr),
+        _ => Err(compile_error(
+            "script enum discriminants have to be integer literals",
+            expr,
+        )),
+    }
+}
+
+fn variant_description(variant: &EnumVariant) -> Option<TokenStream> {
+    variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .map(|attr| {
+            attr.meta
+                .require_name_value()
+                .unwrap()
+                .value
+                .to_token_stream()
+        })
+        .reduce(|mut acc, comment| {
+            acc.extend(quote!(, "\n", ));
+            acc.extend(comment);
+            acc
+        })
+}
@@ -4,15 +4,19 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use darling::FromAttributes;
+use itertools::Itertools;
 use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned, ToTokens};
+use quote::{quote, quote_spanned};
 use syn::{
     parse2, parse_macro_input, spanned::Spanned, FnArg, Ident, ImplItem, ImplItemFn, ItemImpl,
     PatIdent, PatType, ReturnType, Token, Type, Visibility,
 };
 
+use crate::attribute_ops::{RpcModeOpts, RpcOpts, RpcTransferOpts};
 use crate::{
-    extract_ident_from_type, is_context_type, rust_to_variant_type,
+    class_name_metadata, compile_error, extract_ident_from_type, is_context_type,
+    parse_doc_comment, return_class_name_metadata, rust_to_variant_type,
     type_paths::{godot_types, property_hints, string_name_ty, variant_ty},
 };
 
@@ -20,7 +24,7 @@ pub fn godot_script_impl(
     _args: proc_macro::TokenStream,
     body: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    let body = parse_macro_input!(body as ItemImpl);
+    let mut body = parse_macro_input!(body as ItemImpl);
 
     let godot_types = godot_types();
     let string_name_ty = string_name_ty();
@@ -28,9 +32,11 @@ pub fn godot_script_impl(
     let call_error_ty = quote!(#godot_types::sys::GDExtensionCallErrorType);
     let property_hints = property_hints();
 
-    let current_type = &body.self_ty;
+    let self_ty = body.self_ty.clone();
+    let constants_metadata = extract_constants(&mut body, &self_ty);
+    let current_type = &self_ty;
 
-    let result: Result<Vec<(TokenStream, TokenStream)>, _> = body
+    let result: Result<Vec<(TokenStream, TokenStream, TokenStream)>, _> = body
         .items
         .iter()
         .filter_map(|item| match item {
@@ -45,112 +51,269 @@ pub fn godot_script_impl(
                 ty @ ReturnType::Default => syn::parse2::<Type>(quote_spanned!(ty.span() => ())).map_err(|err| err.into_compile_error())?,
                 ReturnType::Type(_, ty) => (**ty).to_owned(),
             };
-            let fn_return_ty = rust_to_variant_type(&fn_return_ty_rust)?;
+            let fn_return_ty = quote_spanned! {
+                fn_return_ty_rust.span() =>
+                <#fn_return_ty_rust as ::godot_rust_script::private_export::ScriptMethodReturn<_>>::variant_type()
+            };
+            let fn_return_class_name = return_class_name_metadata(&fn_return_ty_rust);
             let is_static = !fnc.sig.inputs.iter().any(|arg| matches!(arg, FnArg::Receiver(_)));
 
-            let args: Vec<(TokenStream, TokenStream)> = fnc.sig.inputs
+            let call_args: Vec<&PatType> = fnc.sig.inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::FnArg::Typed(arg) if !is_context_type(arg.ty.as_ref()) => Some(arg),
+                    _ => None,
+                })
+                .collect();
+
+            let is_vararg = call_args.last().is_some_and(|arg| is_vararg_type(arg.ty.as_ref()));
+
+            if let Some(arg) = call_args.iter().rev().skip(1).find(|arg| is_vararg_type(arg.ty.as_ref())) {
+                return Err(compile_error("variadic arguments have to be the last argument of the method", arg).into());
+            }
+
+            if let Some(arg) = call_args.last().filter(|_| is_vararg).filter(|arg| arg.attrs.iter().any(is_default_attr)) {
+                return Err(compile_error("variadic arguments can not have a default value", arg).into());
+            }
+
+            let has_defaults: Vec<bool> = call_args
+                .iter()
+                .filter(|arg| !is_vararg_type(arg.ty.as_ref()))
+                .map(|arg| arg.attrs.iter().any(is_default_attr))
+                .collect();
+
+            if is_static && fnc.sig.inputs.iter().any(|arg| matches!(arg, FnArg::Typed(arg) if is_context_type(arg.ty.as_ref()))) {
+                return Err(compile_error("static methods can not receive a call context", &fnc.sig.inputs).into());
+            }
+
+            if has_defaults.windows(2).any(|pair| pair[0] && !pair[1]) {
+                return Err(compile_error("arguments with a default value have to be at the end of the argument list", &fnc.sig.inputs).into());
+            }
+
+            let required_arg_count = has_defaults.iter().filter(|has_default| !**has_default).count();
+
+            let args: Vec<(TokenStream, TokenStream, TokenStream)> = fnc.sig.inputs
                 .iter()
                 .filter_map(|arg| match arg {
                     syn::FnArg::Typed(arg) => Some(arg),
                     syn::FnArg::Receiver(_) => None
                 })
-                .enumerate()
+                // the context is not passed by the caller, so it does not take up an argument position.
+                .scan(0usize, |position, arg| {
+                    let index = *position;
+
+                    if !is_context_type(arg.ty.as_ref()) {
+                        *position += 1;
+                    }
+
+                    Some((index, arg))
+                })
                 .map(|(index, arg)| {
-                    let arg_name = arg.pat.as_ref();
+                    // all remaining call arguments are passed to the variadic argument as they are.
+                    if is_vararg_type(arg.ty.as_ref()) {
+                        return (
+                            quote!(),
+
+                            quote_spanned!(arg.span() => args.get(#index..).unwrap_or(&[]),),
+
+                            quote!(),
+                        );
+                    }
+
+                    let arg_name = arg_name(arg.pat.as_ref(), index);
                     let arg_rust_type = arg.ty.as_ref();
+                    let arg_class_name = class_name_metadata(arg_rust_type);
                     let arg_type = rust_to_variant_type(arg.ty.as_ref()).unwrap();
 
                     is_context_type(arg.ty.as_ref()).then(|| {
                         (
                             quote!(),
 
-                            quote_spanned!(arg.span() => ctx,)
+                            quote_spanned!(arg.span() => ctx,),
+
+                            quote!(),
                         )
                     }).unwrap_or_else(|| {
+                        let default_value = arg.attrs
+                            .iter()
+                            .find(|attr| is_default_attr(attr))
+                            .map(|attr| attr.parse_args::<syn::Expr>().map_err(|err| err.into_compile_error()).map(|expr| quote_spanned! {
+                                expr.span() =>
+                                {
+                                    let value: #arg_rust_type = #expr;
+                                    value
+                                }
+                            }));
+
+                        let convert_arg = quote_spanned! {
+                            arg.span() =>
+                            #godot_types::prelude::FromGodot::try_from_variant(arg).map_err(|err| {
+                                #godot_types::global::godot_error!("failed to convert variant for argument {} of {}: {}", #arg_name, #fn_name_str,  err);
+                                #godot_types::sys::GDEXTENSION_CALL_ERROR_INVALID_ARGUMENT
+                            })?
+                        };
+
+                        let (dispatch, default_meta) = match default_value {
+                            Some(default_value) => {
+                                let default_value = default_value.unwrap_or_else(|err| err);
+
+                                (
+                                    quote_spanned! {
+                                        arg.span() =>
+                                        match args.get(#index) {
+                                            Some(arg) => #convert_arg,
+                                            None => #default_value,
+                                        },
+                                    },
+
+                                    quote_spanned! {
+                                        arg.span() =>
+                                        (|| #godot_types::prelude::ToGodot::to_variant(&#default_value)) as fn() -> #variant_ty,
+                                    },
+                                )
+                            }
+
+                            None => (
+                                quote_spanned! {
+                                    arg.span() =>
+                                    match args.get(#index) {
+                                        Some(arg) => #convert_arg,
+                                        None => {
+                                            #godot_types::global::godot_error!(
+                                                "too few arguments for {}::{}: expected {}, received {}",
+                                                <Self as ::godot_rust_script::GodotScript>::CLASS_NAME,
+                                                #fn_name_str,
+                                                #required_arg_count,
+                                                args.len(),
+                                            );
+                                            return Err(#godot_types::sys::GDEXTENSION_CALL_ERROR_TOO_FEW_ARGUMENTS);
+                                        }
+                                    },
+                                },
+
+                                quote!(),
+                            ),
+                        };
+
                         (
                             quote_spanned! {
                                 arg.span() =>
                                 ::godot_rust_script::private_export::RustScriptPropDesc {
-                                    name: stringify!(#arg_name),
+                                    name: #arg_name,
                                     ty: #arg_type,
-                                    class_name: <<#arg_rust_type as #godot_types::meta::GodotConvert>::Via as #godot_types::meta::GodotType>::class_name(),
+                                    class_name: #arg_class_name,
                                     exported: false,
+                                    usage: #godot_types::global::PropertyUsageFlags::NONE,
                                     hint: #property_hints::NONE,
                                     hint_string: String::new(),
                                     description: "",
                                 },
                             },
 
-                            quote_spanned! {
-                                arg.span() =>
-                                #godot_types::prelude::FromGodot::try_from_variant(
-                                    args.get(#index).ok_or(#godot_types::sys::GDEXTENSION_CALL_ERROR_TOO_FEW_ARGUMENTS)?
-                                ).map_err(|err| {
-                                    #godot_types::global::godot_error!("failed to convert variant for argument {} of {}: {}", stringify!(#arg_name), #fn_name_str,  err);
-                                    #godot_types::sys::GDEXTENSION_CALL_ERROR_INVALID_ARGUMENT
-                                })?,
-                            }
+                            dispatch,
+
+                            default_meta,
                         )
                     })
                 })
                 .collect();
 
-            let arg_count = args.len();
+            let arg_count = has_defaults.len();
 
-            let (args_meta, args): (TokenStream, TokenStream) = args.into_iter().unzip();
+            let (args_meta, args, default_args_meta): (TokenStream, TokenStream, TokenStream) = args.into_iter().multiunzip();
 
 
-            let dispatch = quote_spanned! {
+            let fn_call = if is_static {
+                quote_spanned!(fnc.span() => Self::#fn_name(#args))
+            } else {
+                quote_spanned!(fnc.span() => self.#fn_name(#args))
+            };
+
+            // only fallible methods return an error, which is reported as a failed call.
+            let fn_result = quote_spanned! {
                 fnc.span() =>
-                #fn_name_str => {
+                match ::godot_rust_script::private_export::ScriptMethodReturn::into_value(#fn_call) {
+                    Ok(value) => Ok(#godot_types::prelude::ToGodot::to_variant(&value)),
+                    Err(err) => {
+                        #godot_types::global::godot_error!("{}::{} failed: {}", <Self as ::godot_rust_script::GodotScript>::CLASS_NAME, #fn_name_str, err);
+                        Err(#godot_types::sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD)
+                    }
+                }
+            };
+
+            let arg_count_check = if is_vararg {
+                TokenStream::default()
+            } else {
+                quote_spanned! {
+                    fnc.span() =>
                     if args.len() > #arg_count {
                         return Err(#godot_types::sys::GDEXTENSION_CALL_ERROR_TOO_MANY_ARGUMENTS);
                     }
+                }
+            };
+
+            let dispatch = quote_spanned! {
+                fnc.span() =>
+                #fn_name_str => {
+                    #arg_count_check
 
-                    Ok(#godot_types::prelude::ToGodot::to_variant(&self.#fn_name(#args)))
+                    #fn_result
                 },
             };
 
-            let method_flag = if is_static {
-                quote!(#godot_types::global::MethodFlags::STATIC)
+            let static_dispatch = if is_static {
+                dispatch.clone()
             } else {
-                quote!(#godot_types::global::MethodFlags::NORMAL)
+                TokenStream::default()
+            };
+
+            let method_flag = match (is_static, is_vararg) {
+                (true, false) => quote!(#godot_types::global::MethodFlags::STATIC),
+                (true, true) => quote!(#godot_types::global::MethodFlags::STATIC | #godot_types::global::MethodFlags::VARARG),
+                (false, false) => quote!(#godot_types::global::MethodFlags::NORMAL),
+                (false, true) => quote!(#godot_types::global::MethodFlags::NORMAL | #godot_types::global::MethodFlags::VARARG),
             };
 
-            let description = fnc.attrs.iter()
-                .filter(|attr| attr.path().is_ident("doc"))
-                .map(|attr| attr.meta.require_name_value().unwrap().value.to_token_stream())
-                .reduce(|mut acc, ident| {
-                    acc.extend(quote!(, "\n", ));
-                    acc.extend(ident);
-                    acc
-                });
+            let rpc = derive_rpc_desc(fnc, is_static)?;
+            let doc_comment = parse_doc_comment(&fnc.attrs);
+            let description = doc_comment.description;
+            let is_deprecated = doc_comment.is_deprecated;
+            let is_experimental = doc_comment.is_experimental;
 
             let metadata = quote_spanned! {
                 fnc.span() =>
                 ::godot_rust_script::private_export::RustScriptMethodDesc {
                     name: #fn_name_str,
                     arguments: Box::new([#args_meta]),
+                    default_arguments: Box::new([#default_args_meta]),
                     return_type: ::godot_rust_script::private_export::RustScriptPropDesc {
                         name: #fn_name_str,
                         ty: #fn_return_ty,
-                        class_name: <<#fn_return_ty_rust as #godot_types::meta::GodotConvert>::Via as #godot_types::meta::GodotType>::class_name(),
+                        class_name: #fn_return_class_name,
                         exported: false,
+                        usage: #godot_types::global::PropertyUsageFlags::NONE,
                         hint: #property_hints::NONE,
                         hint_string: String::new(),
                         description: "",
                     },
                     flags: #method_flag,
                     description: concat!(#description),
+                    is_deprecated: #is_deprecated,
+                    is_experimental: #is_experimental,
+                    rpc: #rpc,
                 },
             };
 
-            Ok((dispatch, metadata))
+            Ok((dispatch, static_dispatch, metadata))
         })
         .collect();
 
-    let (method_dispatch, method_metadata): (TokenStream, TokenStream) = match result {
-        Ok(r) => r.into_iter().unzip(),
+    let (method_dispatch, static_method_dispatch, method_metadata): (
+        TokenStream,
+        TokenStream,
+        TokenStream,
+    ) = match result {
+        Ok(r) => r.into_iter().multiunzip(),
         Err(err) => return err,
     };
 
@@ -167,6 +330,15 @@ pub fn godot_script_impl(
                     _ => Err(#godot_types::sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD),
                 }
             }
+
+            #[allow(unused_variables)]
+            fn call_static_fn(name: #string_name_ty, args: &[&#variant_ty]) -> ::std::result::Result<#variant_ty, #call_error_ty> {
+                match name.to_string().as_str() {
+                    #static_method_dispatch
+
+                    _ => Err(#godot_types::sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD),
+                }
+            }
         }
     };
 
@@ -175,10 +347,16 @@ pub fn godot_script_impl(
             #current_type,
             vec![
                 #method_metadata
+            ],
+            vec![
+                #constants_metadata
             ]
         );
     };
 
+    strip_default_attrs(&mut body);
+    strip_rpc_attrs(&mut body);
+
     let pub_interface = generate_public_interface(&body);
 
     quote! {
@@ -193,6 +371,138 @@ pub fn godot_script_impl(
     .into()
 }
 
+/// Name of a method argument without binding modes like `mut` or `ref`. Destructuring patterns have no single name and
+/// are named after their position.
+fn arg_name(pat: &syn::Pat, index: usize) -> String {
+    match pat {
+        syn::Pat::Ident(pat) => pat.ident.to_string(),
+        syn::Pat::Type(pat) => arg_name(&pat.pat, index),
+        syn::Pat::Paren(pat) => arg_name(&pat.pat, index),
+        _ => format!("arg{index}"),
+    }
+}
+
+/// Variadic method arguments are declared as a trailing `&[&Variant]` slice.
+fn is_vararg_type(ty: &Type) -> bool {
+    let Type::Reference(reference) = ty else {
+        return false;
+    };
+
+    let Type::Slice(slice) = reference.elem.as_ref() else {
+        return false;
+    };
+
+    let Type::Reference(element) = slice.elem.as_ref() else {
+        return false;
+    };
+
+    matches!(element.elem.as_ref(), Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Variant"))
+}
+
+fn is_default_attr(attr: &syn::Attribute) -> bool {
+    attr.path().is_ident("default")
+}
+
+/// Removes the `#[default(...)]` markers from all method arguments, as they are not valid attributes for the compiler.
+fn strip_default_attrs(body: &mut ItemImpl) {
+    body.items
+        .iter_mut()
+        .filter_map(|item| match item {
+            ImplItem::Fn(fnc) => Some(fnc),
+            _ => None,
+        })
+        .flat_map(|fnc| fnc.sig.inputs.iter_mut())
+        .for_each(|arg| match arg {
+            FnArg::Typed(arg) => arg.attrs.retain(|attr| !is_default_attr(attr)),
+            FnArg::Receiver(_) => (),
+        });
+}
+
+/// Removes the `#[rpc(...)]` markers from all methods, they are only read by the macro.
+fn strip_rpc_attrs(body: &mut ItemImpl) {
+    body.items
+        .iter_mut()
+        .filter_map(|item| match item {
+            ImplItem::Fn(fnc) => Some(fnc),
+            _ => None,
+        })
+        .for_each(|fnc| fnc.attrs.retain(|attr| !attr.path().is_ident("rpc")));
+}
+
+/// RPC configuration of a method that is marked with `#[rpc(...)]`.
+fn derive_rpc_desc(fnc: &ImplItemFn, is_static: bool) -> Result<TokenStream, TokenStream> {
+    let Some(attr) = fnc.attrs.iter().find(|attr| attr.path().is_ident("rpc")) else {
+        return Ok(quote!(None));
+    };
+
+    if is_static {
+        return Err(compile_error(
+            "static methods can not be called remotely",
+            attr,
+        ));
+    }
+
+    let opts = RpcOpts::from_attributes(&fnc.attrs).map_err(|err| err.write_errors())?;
+    let godot_types = godot_types();
+    let rpc_mode = quote!(#godot_types::classes::multiplayer_api::RpcMode);
+    let transfer_mode = quote!(#godot_types::classes::multiplayer_peer::TransferMode);
+
+    let mode = match opts.mode {
+        RpcModeOpts::Authority => quote!(#rpc_mode::AUTHORITY),
+        RpcModeOpts::AnyPeer => quote!(#rpc_mode::ANY_PEER),
+    };
+
+    let transfer = match opts.transfer {
+        RpcTransferOpts::Reliable => quote!(#transfer_mode::RELIABLE),
+        RpcTransferOpts::Unreliable => quote!(#transfer_mode::UNRELIABLE),
+        RpcTransferOpts::UnreliableOrdered => quote!(#transfer_mode::UNRELIABLE_ORDERED),
+    };
+
+    let call_local = opts.call_local;
+    let channel = opts.channel;
+
+    Ok(quote_spanned! { attr.span() =>
+        Some(::godot_rust_script::private_export::RustScriptRpcDesc {
+            mode: #mode,
+            transfer_mode: #transfer,
+            call_local: #call_local,
+            channel: #channel,
+        })
+    })
+}
+
+/// Collects all associated consts that are marked with `#[constant]` and removes the marker attribute from the impl body.
+fn extract_constants(body: &mut ItemImpl, current_type: &Type) -> TokenStream {
+    let godot_types = godot_types();
+
+    body.items
+        .iter_mut()
+        .filter_map(|item| match item {
+            ImplItem::Const(item) => Some(item),
+            _ => None,
+        })
+        .filter_map(|item| {
+            let attr_count = item.attrs.len();
+
+            item.attrs.retain(|attr| !attr.path().is_ident("constant"));
+
+            (item.attrs.len() != attr_count).then_some(item)
+        })
+        .map(|item| {
+            let const_name = &item.ident;
+            let const_name_str = const_name.to_string();
+
+            quote_spanned! {
+                item.span() =>
+                ::godot_rust_script::private_export::RustScriptConstDesc {
+                    name: #const_name_str,
+                    value: || #godot_types::prelude::ToGodot::to_variant(&<#current_type>::#const_name),
+                },
+            }
+        })
+        .collect()
+}
+
 fn sanitize_trait_fn_arg(arg: FnArg) -> FnArg {
     match arg {
         FnArg::Receiver(mut rec) => {
@@ -242,13 +552,15 @@ fn generate_public_interface(impl_body: &ItemImpl) -> TokenStream {
         Err(err) => return err,
     };
 
+    let godot_types = godot_types();
     let trait_name = Ident::new(&format!("I{}", script_name), script_name.span());
+    let signals_trait_name = Ident::new(&format!("I{}Signals", script_name), script_name.span());
 
     let functions: Vec<_> = impl_body
         .items
         .iter()
         .filter_map(|func| match func {
-            ImplItem::Fn(func @ ImplItemFn{ vis: Visibility::Public(_), .. })  => Some(func),
+            ImplItem::Fn(func @ ImplItemFn{ vis: Visibility::Public(_), .. }) if func.sig.receiver().is_some() => Some(func),
             _ => None,
         })
         .map(|func| {
@@ -262,25 +574,51 @@ fn generate_public_interface(impl_body: &ItemImpl) -> TokenStream {
                 })
                 .map(sanitize_trait_fn_arg)
                 .collect();
-            sig
+
+            // the interface return type depends on whether the method is fallible, which is resolved through the
+            // inferred kind of `ScriptMethodReturn`.
+            let return_ty = match &sig.output {
+                ReturnType::Type(_, ty) => {
+                    let ty = ty.as_ref().clone();
+
+                    sig.generics.params.push(parse2(quote!(ReturnKind)).unwrap());
+                    sig.generics.make_where_clause().predicates.extend([
+                        parse2::<syn::WherePredicate>(quote_spanned!(ty.span() => #ty: ::godot_rust_script::private_export::ScriptMethodReturn<ReturnKind>)).unwrap(),
+                        parse2(quote_spanned!(ty.span() => <#ty as ::godot_rust_script::private_export::ScriptMethodReturn<ReturnKind>>::Value: #godot_types::meta::FromGodot)).unwrap(),
+                    ]);
+                    sig.output = parse2(quote_spanned!(ty.span() => -> <#ty as ::godot_rust_script::private_export::ScriptMethodReturn<ReturnKind>>::Interface)).unwrap();
+
+                    quote!(<#ty as ::godot_rust_script::private_export::ScriptMethodReturn<ReturnKind>>)
+                }
+                ReturnType::Default => quote!(<() as ::godot_rust_script::private_export::ScriptMethodReturn<::godot_rust_script::private_export::ValueReturn>>),
+            };
+
+            (sig, return_ty)
         })
         .collect();
 
     let function_defs: TokenStream = functions
         .iter()
-        .map(|func| quote_spanned! { func.span() =>  #func; })
+        .map(|(func, _)| quote_spanned! { func.span() =>  #func; })
         .collect();
     let function_impls: TokenStream = functions
         .iter()
-        .map(|func| {
+        .map(|(func, return_ty)| {
             let func_name = func.ident.to_string();
-            let args: TokenStream = func
+            let typed_args: Vec<&PatType> = func
                 .inputs
                 .iter()
                 .filter_map(|arg| match arg {
                     FnArg::Receiver(_) => None,
                     FnArg::Typed(arg) => Some(arg),
                 })
+                .collect();
+
+            let vararg = typed_args.last().filter(|arg| is_vararg_type(arg.ty.as_ref())).map(|arg| arg.pat.clone());
+
+            let args: TokenStream = typed_args
+                .iter()
+                .take(typed_args.len() - usize::from(vararg.is_some()))
                 .map(|arg| {
                     let pat = arg.pat.clone();
 
@@ -290,9 +628,16 @@ fn generate_public_interface(impl_body: &ItemImpl) -> TokenStream {
                 })
                 .collect();
 
+            let args = match vararg {
+                Some(vararg) => quote_spanned! { vararg.span() =>
+                    &[#args].into_iter().chain(#vararg.iter().map(|arg| (*arg).clone())).collect::<Vec<_>>()
+                },
+                None => quote!(&[#args]),
+            };
+
             quote_spanned! { func.span() =>
                 #func {
-                    (*self).call(#func_name, &[#args]).to()
+                    #return_ty::from_call(::godot_rust_script::RsRef::try_call(self, #func_name, #args))
                 }
             }
         })
@@ -301,7 +646,7 @@ fn generate_public_interface(impl_body: &ItemImpl) -> TokenStream {
     quote! {
         #[automatically_derived]
         #[allow(dead_code)]
-        pub trait #trait_name {
+        pub trait #trait_name: #signals_trait_name {
             #function_defs
         }
 
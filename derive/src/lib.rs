@@ -17,9 +17,9 @@ use quote::{quote, quote_spanned, ToTokens};
 use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Ident, Type};
 use type_paths::{godot_types, property_hints, string_name_ty, variant_ty};
 
-use crate::attribute_ops::{FieldExportOps, PropertyOpts};
+use crate::attribute_ops::{ExportCategoryOpts, FieldExportOps, PropertyOpts, SignalOpts};
 
-#[proc_macro_derive(GodotScript, attributes(export, script, prop, signal))]
+#[proc_macro_derive(GodotScript, attributes(export, export_category, script, prop, signal))]
 pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -32,25 +32,76 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
     let base_class = opts
         .base
-        .map(|ident| quote!(#ident))
+        .map(|path| quote!(#path))
         .unwrap_or_else(|| quote!(::godot_rust_script::godot::prelude::RefCounted));
 
+    let on_validate_impl = derive_on_validate(opts.on_validate.as_ref());
+    let configuration_warnings_impl =
+        derive_configuration_warnings(opts.configuration_warnings.as_ref());
+    let dynamic_properties_impl = derive_dynamic_properties(opts.dynamic_properties.as_ref());
+    let property_fallback_impl =
+        derive_property_fallback(opts.get_fallback.as_ref(), opts.set_fallback.as_ref());
+    let doc_comment = parse_doc_comment(&opts.attrs);
+    let is_deprecated = opts.deprecated || doc_comment.is_deprecated;
+    let is_experimental = opts.experimental || doc_comment.is_experimental;
+    let is_tool = opts.tool;
     let script_type_ident = opts.ident;
-    let class_name = script_type_ident.to_string();
+    let class_prefix = opts.prefix.map(|prefix| prefix.value()).unwrap_or_default();
+    let class_name = opts
+        .rename
+        .map(|name| name.value())
+        .unwrap_or_else(|| script_type_ident.to_string());
+    let class_name = format!("{class_prefix}{class_name}");
+    let icon_path = opts.icon.map(|icon| icon.value()).unwrap_or_default();
     let fields = opts.data.take_struct().unwrap().fields;
 
+    let has_base_field = fields
+        .iter()
+        .any(|field| field.ident.as_ref().is_some_and(|ident| ident == "base"));
+
+    if !has_base_field {
+        return compile_error(
+            "GodotScript requires a `base` field, e.g. `base: Gd<<Self as GodotScript>::Base>`",
+            &script_type_ident,
+        )
+        .into();
+    }
+
+    // the base object is only assigned to the `base` field, other fields would be left without a value.
+    let extra_base_fields: TokenStream = fields
+        .iter()
+        .filter(|field| field.ident.as_ref().is_some_and(|ident| ident != "base"))
+        .filter(|field| refers_to_script_base(&field.ty))
+        .map(|field| {
+            compile_error(
+                "only the `base` field can hold the base object of the script",
+                &field.ident,
+            )
+        })
+        .collect();
+
+    if !extra_base_fields.is_empty() {
+        return extra_base_fields.into();
+    }
+
+    let to_string_impl = derive_to_string(opts.to_string.as_ref(), &fields);
+
     let (
         field_metadata,
         signal_metadata,
         get_fields_dispatch,
         set_fields_dispatch,
         export_field_state,
+        default_value_dispatch,
+        getter_default_values,
     ): (
         TokenStream,
         TokenStream,
         TokenStream,
         TokenStream,
         TokenStream,
+        TokenStream,
+        TokenStream,
     ) = fields
         .iter()
         .map(|field| {
@@ -67,7 +118,11 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 || field.attrs.iter().any(|attr| attr.path().is_ident("prop"));
             let is_exported = export_attr.is_some();
             let is_signal = signal_attr.is_some();
+            // invalid export attributes are already reported by the field metadata.
+            let is_readonly = is_exported
+                && FieldExportOps::from_attributes(&field.attrs).is_ok_and(|ops| ops.is_readonly());
 
+            let category_metadata = derive_category_metadata(field);
             let field_metadata = match (is_public, is_exported, is_signal) {
                 (false, false, _) | (true, false, true) => TokenStream::default(),
                 (false, true, _) => {
@@ -87,13 +142,26 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
             let get_field_dispatch = is_public.then(|| derive_get_field_dispatch(field));
             let set_field_dispatch =
-                (is_public && !is_signal).then(|| derive_set_field_dispatch(field));
+                (is_public && !is_signal && !is_readonly).then(|| derive_set_field_dispatch(field));
             let export_field_state =
                 (is_public && !is_signal).then(|| derive_property_state_export(field));
+            let default_value_dispatch = (is_public && !is_signal)
+                .then(|| derive_default_value_dispatch(field, is_exported));
+            let getter_default_value = (is_public && !is_signal && is_exported)
+                .then(|| derive_getter_default_value(field));
 
             let signal_metadata = match (is_public, is_signal) {
                 (false, false) | (true, false) => TokenStream::default(),
-                (true, true) => derive_signal_metadata(field),
+                (true, true) => match SignalOpts::from_attributes(&field.attrs) {
+                    // signals of the base class are already known to the engine.
+                    Ok(opts) if opts.base => TokenStream::default(),
+                    Ok(_) => derive_signal_metadata(field),
+                    Err(err) => {
+                        let err = err.write_errors();
+
+                        quote! {#err,}
+                    }
+                },
                 (false, true) => {
                     let err = compile_error("Signals must be public!", signal_attr);
 
@@ -102,11 +170,13 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             };
 
             (
-                field_metadata,
+                quote!(#category_metadata #field_metadata),
                 signal_metadata,
                 get_field_dispatch.to_token_stream(),
                 set_field_dispatch.to_token_stream(),
                 export_field_state.to_token_stream(),
+                default_value_dispatch.to_token_stream(),
+                getter_default_value.to_token_stream(),
             )
         })
         .multiunzip();
@@ -114,24 +184,17 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let get_fields_impl = derive_get_fields(get_fields_dispatch);
     let set_fields_impl = derive_set_fields(set_fields_dispatch);
     let properties_state_impl = derive_property_states_export(export_field_state);
-    let default_impl = derive_default_with_base(&fields);
-
-    let description = opts
-        .attrs
+    let default_impl = derive_default_with_base(&fields, &base_class);
+    let missing_required_exports_impl = derive_missing_required_exports(&fields);
+    let default_values_impl =
+        derive_property_default_values(default_value_dispatch, getter_default_values);
+    let signal_interface = derive_signal_interface(&script_type_ident, &fields);
+
+    let description = doc_comment.description;
+    let tutorials = doc_comment
+        .tutorials
         .iter()
-        .filter(|attr| attr.path().is_ident("doc"))
-        .map(|attr| {
-            attr.meta
-                .require_name_value()
-                .unwrap()
-                .value
-                .to_token_stream()
-        })
-        .reduce(|mut acc, lit| {
-            acc.extend(quote!(,"\n",));
-            acc.extend(lit);
-            acc
-        });
+        .map(|(title, link)| quote!((#title, #link)));
 
     let output = quote! {
         impl ::godot_rust_script::GodotScript for #script_type_ident {
@@ -139,6 +202,10 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 
             const CLASS_NAME: &'static str = #class_name;
 
+            fn class_name() -> #godot_types::meta::ClassName {
+                ::godot_rust_script::script_class_name!(#script_type_ident)
+            }
+
             #get_fields_impl
 
             #set_fields_impl
@@ -147,19 +214,35 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 ::godot_rust_script::GodotScriptImpl::call_fn(self, name, args, ctx)
             }
 
-            fn to_string(&self) -> String {
-                format!("{:?}", self)
-            }
+            #to_string_impl
+
+            #on_validate_impl
+
+            #missing_required_exports_impl
+
+            #configuration_warnings_impl
+
+            #dynamic_properties_impl
+
+            #property_fallback_impl
 
             #properties_state_impl
 
             #default_impl
+
+            #default_values_impl
         }
 
         ::godot_rust_script::register_script_class!(
             #script_type_ident,
             #base_class,
+            #class_prefix,
+            #icon_path,
             concat!(#description),
+            &[#(#tutorials),*],
+            #is_deprecated,
+            #is_experimental,
+            #is_tool,
             vec![
                 #field_metadata
             ],
@@ -168,6 +251,8 @@ pub fn derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             ]
         );
 
+        #signal_interface
+
     };
 
     output.into()
@@ -187,11 +272,17 @@ fn rust_to_variant_type(ty: &syn::Type) -> Result<TokenStream, TokenStream> {
                 <<#path as #godot_types::meta::GodotConvert>::Via as GodotType>::Ffi::variant_type()
             }
         }),
-        T::Verbatim(_) => Err(syn::Error::new(
-            ty.span(),
-            "not sure how to handle verbatim types yet!",
-        )
-        .into_compile_error()),
+        // macro generated types can be wrapped in invisible groups or passed on as raw tokens.
+        T::Group(group) => rust_to_variant_type(&group.elem),
+        T::Paren(paren) => rust_to_variant_type(&paren.elem),
+        T::Verbatim(tokens) => match syn::parse2::<syn::Type>(tokens.clone()) {
+            Ok(T::Verbatim(_)) | Err(_) => Err(syn::Error::new(
+                ty.span(),
+                format!("\"{}\" is not a supported type", tokens),
+            )
+            .into_compile_error()),
+            Ok(parsed) => rust_to_variant_type(&parsed),
+        },
         T::Tuple(tuple) => {
             if !tuple.elems.is_empty() {
                 return Err(syn::Error::new(
@@ -218,6 +309,33 @@ fn rust_to_variant_type(ty: &syn::Type) -> Result<TokenStream, TokenStream> {
     }
 }
 
+/// Class name of a method argument. Types that implement `GodotScriptExport` provide their own class name, e.g. script
+/// references are described with the class of the script instead of the class of their base object.
+fn class_name_metadata(ty: &syn::Type) -> TokenStream {
+    class_name_of(
+        ty,
+        quote_spanned!(ty.span() => ::godot_rust_script::private_export::ClassNameOf::<#ty>::default()),
+    )
+}
+
+/// Class name of the value that a method with the return type `ty` passes to the engine.
+fn return_class_name_metadata(ty: &syn::Type) -> TokenStream {
+    class_name_of(
+        ty,
+        quote_spanned!(ty.span() => ::godot_rust_script::private_export::ClassNameOf::method_return::<#ty, _>()),
+    )
+}
+
+fn class_name_of(ty: &syn::Type, probe: TokenStream) -> TokenStream {
+    quote_spanned! {ty.span() =>
+        {
+            use ::godot_rust_script::private_export::{ConvertClassName as _, ExportClassName as _};
+
+            (&&#probe).class_name()
+        }
+    }
+}
+
 fn is_context_type(ty: &syn::Type) -> bool {
     let syn::Type::Path(path) = ty else {
         return false;
@@ -230,8 +348,63 @@ fn is_context_type(ty: &syn::Type) -> bool {
         .unwrap_or(false)
 }
 
-fn derive_default_with_base(field_opts: &[SpannedValue<FieldOpts>]) -> TokenStream {
+/// Whether the type refers to the base class of the script, e.g. `Gd<<Self as GodotScript>::Base>`.
+fn refers_to_script_base(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => {
+            let is_self = |ty: &Type| matches!(ty, Type::Path(path) if path.path.is_ident("Self"));
+            let is_base_assoc = path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Base")
+                && match &path.qself {
+                    Some(qself) => is_self(&qself.ty),
+                    None => path
+                        .path
+                        .segments
+                        .first()
+                        .is_some_and(|segment| segment.ident == "Self"),
+                };
+
+            is_base_assoc
+                || path
+                    .path
+                    .segments
+                    .iter()
+                    .any(|segment| match &segment.arguments {
+                        syn::PathArguments::AngleBracketed(args) => {
+                            args.args.iter().any(|arg| {
+                                matches!(arg, syn::GenericArgument::Type(ty) if refers_to_script_base(ty))
+                            })
+                        }
+                        _ => false,
+                    })
+        }
+        Type::Group(group) => refers_to_script_base(&group.elem),
+        Type::Paren(paren) => refers_to_script_base(&paren.elem),
+        _ => false,
+    }
+}
+
+fn derive_default_with_base(
+    field_opts: &[SpannedValue<FieldOpts>],
+    base_class: &TokenStream,
+) -> TokenStream {
     let godot_types = godot_types();
+
+    // the base field has to hold the declared base class or one of its ancestors, otherwise the cast would fail at
+    // runtime.
+    let base_field_check = field_opts
+        .iter()
+        .find(|field| field.ident.as_ref().is_some_and(|ident| ident == "base"))
+        .map(|field| {
+            let field_ty = &field.ty;
+
+            quote_spanned! {field_ty.span()=>
+                let _ = |base: #godot_types::prelude::Gd<#base_class>| -> #field_ty { base.upcast() };
+            }
+        });
     let fields: TokenStream = field_opts
         .iter()
         .filter_map(|field| match field.ident.as_ref() {
@@ -243,13 +416,25 @@ fn derive_default_with_base(field_opts: &[SpannedValue<FieldOpts>]) -> TokenStre
                 Some(quote_spanned!(ident.span() => #ident: ::godot_rust_script::ScriptSignal::new(base.clone(), stringify!(#ident)),))
             }
 
-            Some(ident) => Some(quote_spanned!(ident.span() => #ident: Default::default(),)),
+            Some(ident) => {
+                // invalid prop attributes are already reported by the property dispatch.
+                let default = PropertyOpts::from_attributes(&field.attrs)
+                    .ok()
+                    .and_then(|opts| opts.default);
+
+                match default {
+                    Some(default) => Some(quote_spanned!(default.span() => #ident: #default,)),
+                    None => Some(quote_spanned!(ident.span() => #ident: Default::default(),)),
+                }
+            }
             None => None,
         })
         .collect();
 
     quote! {
         fn default_with_base(base: #godot_types::prelude::Gd<#godot_types::prelude::Object>) -> Self {
+            #base_field_check
+
             Self {
                 #fields
             }
@@ -257,6 +442,271 @@ fn derive_default_with_base(field_opts: &[SpannedValue<FieldOpts>]) -> TokenStre
     }
 }
 
+fn derive_default_value_dispatch(
+    field: &SpannedValue<FieldOpts>,
+    is_exported: bool,
+) -> TokenStream {
+    let godot_types = godot_types();
+
+    let field_ident = field.ident.as_ref().unwrap();
+    let field_name = field_ident.to_string();
+    let field_ty = &field.ty;
+
+    // invalid prop attributes are already reported by the property dispatch.
+    let Ok(opts) = PropertyOpts::from_attributes(&field.attrs) else {
+        return TokenStream::default();
+    };
+
+    // properties with a custom getter are resolved once per class by `property_getter_defaults`.
+    if opts.get.is_some() {
+        return TokenStream::default();
+    }
+
+    let default = match opts.default {
+        Some(default) => default,
+        None if is_exported => {
+            syn::parse_quote_spanned!(field_ty.span()=> ::std::default::Default::default())
+        }
+        None => return TokenStream::default(),
+    };
+
+    // values that have to be assigned in the editor have no default.
+    let required_check = is_exported.then(|| {
+        quote_spanned! {field_ty.span()=>
+            if <#field_ty as ::godot_rust_script::GodotScriptExport>::REQUIRED {
+                return None;
+            }
+        }
+    });
+
+    quote_spanned! {default.span()=>
+        #field_name => {
+            #required_check
+
+            let value: #field_ty = #default;
+
+            Some(#godot_types::prelude::ToGodot::to_variant(&value))
+        },
+    }
+}
+
+fn derive_getter_default_value(field: &SpannedValue<FieldOpts>) -> TokenStream {
+    let godot_types = godot_types();
+    let string_name_ty = string_name_ty();
+
+    let field_name = field.ident.as_ref().unwrap().to_string();
+    let field_ty = &field.ty;
+
+    let Ok(PropertyOpts {
+        get: Some(getter), ..
+    }) = PropertyOpts::from_attributes(&field.attrs)
+    else {
+        return TokenStream::default();
+    };
+
+    // values that have to be assigned in the editor have no default.
+    quote_spanned! {getter.span()=>
+        if !<#field_ty as ::godot_rust_script::GodotScriptExport>::REQUIRED {
+            defaults.insert(
+                #string_name_ty::from(#field_name),
+                #godot_types::prelude::ToGodot::to_variant(&#getter(script)),
+            );
+        }
+    }
+}
+
+fn derive_missing_required_exports(field_opts: &[SpannedValue<FieldOpts>]) -> TokenStream {
+    let string_name_ty = string_name_ty();
+
+    let checks: TokenStream = field_opts
+        .iter()
+        .filter(|field| {
+            field
+                .attrs
+                .iter()
+                .any(|attr| attr.path().is_ident("export"))
+        })
+        .filter_map(|field| field.ident.as_ref())
+        .map(|ident| {
+            let name = ident.to_string();
+
+            quote_spanned! {ident.span()=>
+                if !::godot_rust_script::GodotScriptExport::is_assigned(&self.#ident) {
+                    missing.push(#string_name_ty::from(#name));
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        fn missing_required_exports(&self) -> ::std::vec::Vec<#string_name_ty> {
+            #[allow(unused_mut)]
+            let mut missing = ::std::vec::Vec::new();
+
+            #checks
+
+            missing
+        }
+    }
+}
+
+fn derive_configuration_warnings(configuration_warnings: Option<&syn::Expr>) -> TokenStream {
+    let godot_types = godot_types();
+
+    let body = match configuration_warnings {
+        Some(warnings) => quote_spanned!(warnings.span()=> #warnings(self)),
+        None => quote!(#godot_types::builtin::PackedStringArray::new()),
+    };
+
+    quote! {
+        fn configuration_warnings(&self) -> #godot_types::builtin::PackedStringArray {
+            #body
+        }
+    }
+}
+
+/// The string representation of script instances. Without a custom function the fields of the script are listed
+/// after the class name and instance id, the same way the engine prints native objects. The base field is left out,
+/// because it only repeats the object that is already described by the prefix.
+fn derive_to_string(
+    to_string: Option<&syn::Expr>,
+    fields: &[SpannedValue<FieldOpts>],
+) -> TokenStream {
+    if let Some(to_string) = to_string {
+        return quote_spanned! {to_string.span()=>
+            fn to_string(&self) -> String {
+                #to_string(self)
+            }
+        };
+    }
+
+    let field_idents: Vec<_> = fields
+        .iter()
+        .filter_map(|field| field.ident.as_ref())
+        .filter(|ident| *ident != "base")
+        .collect();
+
+    let prefix = quote! {
+        ::godot_rust_script::private_export::script_instance_label(
+            <Self as ::godot_rust_script::GodotScript>::CLASS_NAME,
+            self.base.instance_id(),
+        )
+    };
+
+    let body = if field_idents.is_empty() {
+        prefix
+    } else {
+        let field_names = field_idents.iter().map(|ident| ident.to_string());
+
+        quote! {
+            let fields = [
+                #(format!("{}: {:?}", #field_names, self.#field_idents)),*
+            ];
+
+            format!("{} {{ {} }}", #prefix, fields.join(", "))
+        }
+    };
+
+    quote! {
+        fn to_string(&self) -> String {
+            #body
+        }
+    }
+}
+
+fn derive_on_validate(on_validate: Option<&syn::Expr>) -> TokenStream {
+    let body = on_validate.map(|validate| quote_spanned!(validate.span()=> #validate(self);));
+
+    quote! {
+        fn on_validate(&mut self) {
+            #body
+        }
+    }
+}
+
+fn derive_dynamic_properties(dynamic_properties: Option<&syn::Expr>) -> TokenStream {
+    let godot_types = godot_types();
+
+    let body = match dynamic_properties {
+        Some(properties) => quote_spanned!(properties.span()=> #properties(self)),
+        None => quote!(None),
+    };
+
+    quote! {
+        fn dynamic_properties(&self) -> ::std::option::Option<::std::vec::Vec<#godot_types::meta::PropertyInfo>> {
+            #body
+        }
+    }
+}
+
+fn derive_property_fallback(
+    get_fallback: Option<&syn::Expr>,
+    set_fallback: Option<&syn::Expr>,
+) -> TokenStream {
+    let string_name_ty = string_name_ty();
+    let variant_ty = variant_ty();
+
+    let get_body = match get_fallback {
+        Some(getter) => quote_spanned!(getter.span()=> #getter(self, name)),
+        None => quote!(None),
+    };
+
+    let set_body = match set_fallback {
+        Some(setter) => quote_spanned!(setter.span()=> #setter(self, name, value)),
+        None => quote!(false),
+    };
+
+    quote! {
+        #[allow(unused_variables)]
+        fn get_fallback(&self, name: #string_name_ty) -> ::std::option::Option<#variant_ty> {
+            #get_body
+        }
+
+        #[allow(unused_variables)]
+        fn set_fallback(&mut self, name: #string_name_ty, value: #variant_ty) -> bool {
+            #set_body
+        }
+    }
+}
+
+fn derive_property_default_values(
+    default_value_dispatch: TokenStream,
+    getter_default_values: TokenStream,
+) -> TokenStream {
+    let string_name_ty = string_name_ty();
+    let variant_ty = variant_ty();
+
+    // only create a default instance of the script if there are getters to call.
+    let getter_defaults_body = if getter_default_values.is_empty() {
+        quote!(::std::collections::HashMap::new())
+    } else {
+        quote! {
+            ::godot_rust_script::private_export::with_default_script(|script: &Self| {
+                let mut defaults = ::std::collections::HashMap::new();
+
+                #getter_default_values
+
+                defaults
+            })
+            .unwrap_or_default()
+        }
+    };
+
+    quote! {
+        fn property_default_value(name: #string_name_ty) -> ::std::option::Option<#variant_ty> {
+            match name.to_string().as_str() {
+                #default_value_dispatch
+
+                _ => None,
+            }
+        }
+
+        fn property_getter_defaults() -> ::std::collections::HashMap<#string_name_ty, #variant_ty> {
+            #getter_defaults_body
+        }
+    }
+}
+
 fn derive_get_field_dispatch(field: &SpannedValue<FieldOpts>) -> TokenStream {
     let godot_types = godot_types();
 
@@ -312,13 +762,45 @@ fn derive_set_field_dispatch(field: &SpannedValue<FieldOpts>) -> TokenStream {
         None => quote_spanned!(field.ty.span() => self.#field_ident = local_value),
     };
 
+    let field_ty = &field.ty;
+
+    // invalid export attributes are already reported by the field metadata.
+    let scene_root_check = FieldExportOps::from_attributes(&field.attrs)
+        .ok()
+        .and_then(|ops| ops.scene_root().map(str::to_owned))
+        .map(|scene_root| {
+            quote_spanned! {field.span()=>
+                ::godot_rust_script::private_export::validate_scene_root(
+                    <Self as ::godot_rust_script::GodotScript>::CLASS_NAME,
+                    #field_name,
+                    &value,
+                    #scene_root,
+                );
+            }
+        });
+
     quote! {
         #field_name => {
             let local_value = match #variant_value {
                 Ok(v) => v,
-                Err(_) => return false,
+                Err(err) => {
+                    // the editor probes properties with nil values, these are rejected silently.
+                    if !value.is_nil() {
+                        #godot_types::global::godot_warn!(
+                            "{}: unable to assign {:?} value to property \"{}\" of type {}: {}",
+                            <Self as ::godot_rust_script::GodotScript>::CLASS_NAME,
+                            value.get_type(),
+                            #field_name,
+                            ::std::any::type_name::<#field_ty>(),
+                            err,
+                        );
+                    }
+
+                    return false;
+                }
             };
 
+            #scene_root_check
             #assignment;
             true
         },
@@ -383,18 +865,21 @@ fn derive_field_metadata(
     let rust_ty = &field.ty;
     let ty = rust_to_variant_type(&field.ty)?;
 
-    let (hint, hint_string) = is_exported
+    let (hint, hint_string, usage) = is_exported
         .then(|| {
             let ops =
                 FieldExportOps::from_attributes(&field.attrs).map_err(|err| err.write_errors())?;
 
             ops.hint(&field.ty)
+                .map(|(hint, hint_string)| (hint, hint_string, ops.usage()))
         })
         .transpose()?
         .unwrap_or_else(|| {
             (
                 quote_spanned!(field.span()=> #property_hint_ty::NONE),
                 quote_spanned!(field.span()=> String::new()),
+                // public fields are persisted like script variables in GDScript, but they are not shown in the inspector.
+                quote_spanned!(field.span()=> #godot_types::global::PropertyUsageFlags::SCRIPT_VARIABLE | #godot_types::global::PropertyUsageFlags::STORAGE),
             )
         });
 
@@ -405,6 +890,7 @@ fn derive_field_metadata(
             ty: #ty,
             class_name: <<#rust_ty as #godot_types::meta::GodotConvert>::Via as #godot_types::meta::GodotType>::class_name(),
             exported: #is_exported,
+            usage: #usage,
             hint: #hint,
             hint_string: #hint_string,
             description: concat!(#description),
@@ -414,6 +900,43 @@ fn derive_field_metadata(
     Ok(item)
 }
 
+/// Categories are inserted into the property list in front of the field they are declared on and group all following
+/// properties until the next category.
+fn derive_category_metadata(field: &SpannedValue<FieldOpts>) -> TokenStream {
+    let godot_types = godot_types();
+    let property_hint_ty = property_hints();
+
+    if !field
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("export_category"))
+    {
+        return TokenStream::default();
+    }
+
+    let name = match ExportCategoryOpts::from_attributes(&field.attrs) {
+        Ok(opts) => opts.name,
+        Err(err) => {
+            let err = err.write_errors();
+
+            return quote! {#err,};
+        }
+    };
+
+    quote_spanned! { name.span() =>
+        ::godot_rust_script::private_export::RustScriptPropDesc {
+            name: #name,
+            ty: #godot_types::builtin::VariantType::NIL,
+            class_name: #godot_types::meta::ClassName::none(),
+            exported: false,
+            usage: #godot_types::global::PropertyUsageFlags::CATEGORY,
+            hint: #property_hint_ty::NONE,
+            hint_string: String::new(),
+            description: "",
+        },
+    }
+}
+
 fn get_field_description(field: &FieldOpts) -> Option<TokenStream> {
     field
         .attrs
@@ -451,6 +974,89 @@ fn derive_signal_metadata(field: &SpannedValue<FieldOpts>) -> TokenStream {
     }
 }
 
+/// Generates the `I{Script}Signals` trait with a typed `emit_{signal}` method for every public signal of the script.
+fn derive_signal_interface(
+    script_type_ident: &Ident,
+    fields: &[SpannedValue<FieldOpts>],
+) -> TokenStream {
+    let trait_name = Ident::new(
+        &format!("I{}Signals", script_type_ident),
+        script_type_ident.span(),
+    );
+
+    let (function_defs, function_impls): (TokenStream, TokenStream) = fields
+        .iter()
+        .filter(|field| matches!(field.vis, syn::Visibility::Public(_)))
+        .filter(|field| field.attrs.iter().any(|attr| attr.path().is_ident("signal")))
+        .filter_map(|field| {
+            let ident = field.ident.as_ref()?;
+            let signal_name = ident.to_string();
+            let signal_type = &field.ty;
+            let (arg_types, is_tuple) = match first_type_argument(signal_type)? {
+                Type::Tuple(tuple) => (tuple.elems.iter().collect(), true),
+                arg_type => (vec![arg_type], false),
+            };
+            let arg_names: Vec<_> = (0..arg_types.len())
+                .map(|index| Ident::new(&format!("arg{index}"), ident.span()))
+                .collect();
+            let args = if is_tuple {
+                quote!((#(#arg_names,)*))
+            } else {
+                quote!(#(#arg_names)*)
+            };
+
+            let fn_name = Ident::new(&format!("emit_{signal_name}"), ident.span());
+            let sig = quote_spanned! { ident.span() =>
+                fn #fn_name(&self, #(#arg_names: #arg_types),*)
+            };
+
+            let def = quote! { #sig; };
+            let body = quote_spanned! { ident.span() =>
+                #sig {
+                    let host = ::std::clone::Clone::clone(&**self).upcast::<::godot_rust_script::godot::classes::Object>();
+
+                    ::godot_rust_script::ScriptSignal::emit(
+                        &<#signal_type as ::godot_rust_script::ScriptSignal>::new(host, #signal_name),
+                        #args,
+                    );
+                }
+            };
+
+            Some((def, body))
+        })
+        .unzip();
+
+    quote! {
+        #[automatically_derived]
+        #[allow(dead_code)]
+        pub trait #trait_name {
+            #function_defs
+        }
+
+        #[automatically_derived]
+        #[allow(dead_code)]
+        impl #trait_name for ::godot_rust_script::RsRef<#script_type_ident> {
+            #function_impls
+        }
+    }
+}
+
+/// The first generic type argument of a type, e.g. `T` of `Signal<T>`.
+fn first_type_argument(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let syn::PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+
+    match args.args.first()? {
+        syn::GenericArgument::Type(arg_type) => Some(arg_type),
+        _ => None,
+    }
+}
+
 #[proc_macro_attribute]
 pub fn godot_script_impl(
     args: proc_macro::TokenStream,
@@ -459,6 +1065,99 @@ pub fn godot_script_impl(
     impl_attribute::godot_script_impl(args, body)
 }
 
+/// Documentation of an item, collected from its doc comments.
+struct DocComment {
+    description: Option<TokenStream>,
+    /// Title and link of every `@tutorial` tag.
+    tutorials: Vec<(String, String)>,
+    is_deprecated: bool,
+    is_experimental: bool,
+}
+
+/// Collects the doc comments of an item and extracts the `@tutorial(Title): https://...`, `@deprecated` and
+/// `@experimental` tags that Godot supports in GDScript doc comments. Lines with unknown tags stay part of the
+/// description.
+fn parse_doc_comment(attrs: &[syn::Attribute]) -> DocComment {
+    let mut tutorials = Vec::new();
+    let mut is_deprecated = false;
+    let mut is_experimental = false;
+
+    let description = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .map(|attr| &attr.meta.require_name_value().unwrap().value)
+        .filter(|value| {
+            let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(line),
+                ..
+            }) = value
+            else {
+                return true;
+            };
+
+            let line = line.value();
+            let line = line.trim();
+
+            if is_doc_tag(line, "@deprecated") {
+                is_deprecated = true;
+                return false;
+            }
+
+            if is_doc_tag(line, "@experimental") {
+                is_experimental = true;
+                return false;
+            }
+
+            match parse_tutorial_tag(line) {
+                Some(tutorial) => {
+                    tutorials.push(tutorial);
+                    false
+                }
+                None => true,
+            }
+        })
+        .map(|value| value.to_token_stream())
+        .reduce(|mut acc, lit| {
+            acc.extend(quote!(,"\n",));
+            acc.extend(lit);
+            acc
+        });
+
+    DocComment {
+        description,
+        tutorials,
+        is_deprecated,
+        is_experimental,
+    }
+}
+
+fn is_doc_tag(line: &str, tag: &str) -> bool {
+    line.strip_prefix(tag)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(':'))
+}
+
+/// Parses `@tutorial: https://...` and `@tutorial(Title): https://...` into the title and the link of the tutorial.
+fn parse_tutorial_tag(line: &str) -> Option<(String, String)> {
+    let rest = line.strip_prefix("@tutorial")?;
+
+    let (title, rest) = match rest.strip_prefix('(') {
+        Some(rest) => {
+            let (title, rest) = rest.split_once(')')?;
+
+            (title.trim(), rest)
+        }
+        None => ("", rest),
+    };
+
+    let link = rest.strip_prefix(':')?.trim();
+
+    if link.is_empty() {
+        return None;
+    }
+
+    Some((title.to_owned(), link.to_owned()))
+}
+
 fn compile_error(message: &str, tokens: impl ToTokens) -> TokenStream {
     syn::Error::new_spanned(tokens, message).into_compile_error()
 }
@@ -6,12 +6,17 @@
 
 use godot::classes::{EditorInterface, Engine};
 use godot::global::godot_warn;
+#[cfg(before_api = "4.4")]
 use godot::meta::ToGodot;
+#[cfg(before_api = "4.4")]
 use godot::prelude::GodotConvert;
 
 #[derive(Clone, Copy)]
 pub enum EditorToasterSeverity {
+    #[allow(dead_code)]
+    Info,
     Warning,
+    Error,
 }
 
 impl From<EditorToasterSeverity> for u8 {
@@ -19,15 +24,32 @@ impl From<EditorToasterSeverity> for u8 {
         use EditorToasterSeverity::*;
 
         match value {
+            Info => 0,
             Warning => 1,
+            Error => 2,
         }
     }
 }
 
+#[cfg(since_api = "4.4")]
+impl From<EditorToasterSeverity> for godot::classes::editor_toaster::Severity {
+    fn from(value: EditorToasterSeverity) -> Self {
+        use EditorToasterSeverity::*;
+
+        match value {
+            Info => Self::INFO,
+            Warning => Self::WARNING,
+            Error => Self::ERROR,
+        }
+    }
+}
+
+#[cfg(before_api = "4.4")]
 impl GodotConvert for EditorToasterSeverity {
     type Via = u8;
 }
 
+#[cfg(before_api = "4.4")]
 impl ToGodot for EditorToasterSeverity {
     type ToVia<'v> = Self::Via;
 
@@ -36,6 +58,25 @@ impl ToGodot for EditorToasterSeverity {
     }
 }
 
+#[cfg(since_api = "4.4")]
+pub fn show_editor_toast(message: &str, severity: EditorToasterSeverity) {
+    if !Engine::singleton().is_editor_hint() {
+        return;
+    }
+
+    let Some(mut editor_toaster) = EditorInterface::singleton().get_editor_toaster() else {
+        godot_warn!("[godot-rust-script] unable to access editor toast notifications!");
+        return;
+    };
+
+    editor_toaster
+        .push_toast_ex(message)
+        .severity(severity.into())
+        .done();
+}
+
+/// Before Godot 4.4 the editor toaster is not exposed, the internal `_popup_str` method has to be called instead.
+#[cfg(before_api = "4.4")]
 pub fn show_editor_toast(message: &str, severity: EditorToasterSeverity) {
     if !Engine::singleton().is_editor_hint() {
         return;
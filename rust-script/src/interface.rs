@@ -4,27 +4,47 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-mod export;
+pub(crate) mod export;
+pub(crate) mod method_return;
+mod on_editor;
+mod rs_ref_array;
+mod script_enum_array;
+mod script_guard;
 mod signals;
+#[cfg(since_api = "4.4")]
+mod typed_dictionary;
 
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 use std::{collections::HashMap, fmt::Debug};
 
-use godot::meta::{FromGodot, GodotConvert, ToGodot};
-use godot::obj::Inherits;
-use godot::prelude::{Gd, Object, StringName, Variant};
+use godot::meta::error::CallError;
+use godot::meta::{ClassName, ConvertError, FromGodot, GodotConvert, PropertyInfo, ToGodot};
+use godot::obj::{GodotClass, Inherits, InstanceId};
+use godot::prelude::{Array, Gd, Object, PackedStringArray, StringName, Variant, VariantArray};
+
+use crate::runtime::InstanceDataCell;
 
 pub use crate::runtime::Context;
 
-pub use export::GodotScriptExport;
+pub use export::{ClearableExport, GodotScriptExport};
+pub use on_editor::OnEditor;
+pub use rs_ref_array::RsRefArray;
+pub use script_enum_array::ScriptEnumArray;
+pub use script_guard::{ScriptMut, ScriptRef};
 pub use signals::{ScriptSignal, Signal};
+#[cfg(since_api = "4.4")]
+pub use typed_dictionary::TypedDictionary;
 
 pub trait GodotScript: Debug + GodotScriptImpl<ImplBase = Self::Base> {
     type Base: Inherits<Object>;
 
     const CLASS_NAME: &'static str;
 
+    /// Class name of the script, as it is registered with the engine.
+    fn class_name() -> ClassName;
+
     fn set(&mut self, name: StringName, value: Variant) -> bool;
     fn get(&self, name: StringName) -> Option<Variant>;
     fn call(
@@ -34,10 +54,37 @@ pub trait GodotScript: Debug + GodotScriptImpl<ImplBase = Self::Base> {
         context: Context<'_, Self>,
     ) -> Result<Variant, godot::sys::GDExtensionCallErrorType>;
 
+    /// String representation of the script instance, used when the object is printed. Set with
+    /// `#[script(to_string = ...)]`, defaults to `<ClassName#instance_id>` followed by the script fields.
     fn to_string(&self) -> String;
     fn property_state(&self) -> HashMap<StringName, Variant>;
 
+    /// Called after a property has been changed in the editor inspector. Set with `#[script(on_validate = ...)]`.
+    fn on_validate(&mut self);
+
+    /// Properties that depend on the state of the script and are listed in addition to the script fields. Set with
+    /// `#[script(dynamic_properties = ...)]`.
+    fn dynamic_properties(&self) -> Option<Vec<PropertyInfo>>;
+
+    /// Reads properties that are not script fields, like `_get` in GDScript. Set with `#[script(get_fallback = ...)]`.
+    fn get_fallback(&self, name: StringName) -> Option<Variant>;
+
+    /// Writes properties that are not script fields, like `_set` in GDScript. Set with `#[script(set_fallback = ...)]`.
+    fn set_fallback(&mut self, name: StringName, value: Variant) -> bool;
+
+    /// Names of all [`OnEditor`] fields that have not been assigned yet.
+    fn missing_required_exports(&self) -> Vec<StringName>;
+
+    /// Warnings that are shown on the node in the scene tree dock, like `_get_configuration_warnings` in GDScript. Set
+    /// with `#[script(configuration_warnings = ...)]`.
+    fn configuration_warnings(&self) -> PackedStringArray;
+
     fn default_with_base(base: godot::prelude::Gd<godot::prelude::Object>) -> Self;
+
+    fn property_default_value(name: StringName) -> Option<Variant>;
+
+    /// Default values of all exported properties with a custom getter, read from a default instance of the script.
+    fn property_getter_defaults() -> HashMap<StringName, Variant>;
 }
 
 pub trait GodotScriptImpl {
@@ -49,6 +96,12 @@ pub trait GodotScriptImpl {
         args: &[&Variant],
         context: Context<Self>,
     ) -> Result<Variant, godot::sys::GDExtensionCallErrorType>;
+
+    /// Dispatches calls to methods of the script that do not take a receiver.
+    fn call_static_fn(
+        name: StringName,
+        args: &[&Variant],
+    ) -> Result<Variant, godot::sys::GDExtensionCallErrorType>;
 }
 
 #[derive(Debug)]
@@ -65,25 +118,130 @@ impl<T: GodotScript> RsRef<T> {
         }
     }
 
-    fn validate_script<O: Inherits<Object>>(owner: &Gd<O>) -> Option<GodotScriptCastError> {
-        let script = owner
-            .upcast_ref::<Object>()
-            .get_script()
-            .try_to::<Option<Gd<crate::runtime::RustScript>>>();
+    /// Converts an array of objects into script references. Fails with the error of the first element that does not
+    /// carry the script `T`.
+    pub fn try_from_array<B: Inherits<T::Base> + Inherits<Object>>(
+        array: &Array<Gd<B>>,
+    ) -> Result<Vec<Self>, GodotScriptCastError> {
+        array
+            .iter_shared()
+            .map(CastToScript::<T>::try_into_script)
+            .collect()
+    }
 
-        let Ok(script) = script else {
-            return Some(GodotScriptCastError::NotRustScript);
-        };
+    /// Converts a variant array into script references. Elements that are not objects carrying the script `T` are
+    /// reported as conversion errors.
+    pub fn try_from_variant_array(array: &VariantArray) -> Result<Vec<Self>, ConvertError> {
+        array
+            .iter_shared()
+            .map(|item| item.try_to::<Self>())
+            .collect()
+    }
+
+    pub fn instance_id(&self) -> InstanceId {
+        self.owner.instance_id()
+    }
+
+    /// Casts the reference to a reference of the script `U`, like [`Gd::try_cast`] does for engine classes. Fails if
+    /// the object does not carry the script `U`.
+    pub fn try_cast<U: GodotScript>(self) -> Result<RsRef<U>, GodotScriptCastError> {
+        if let Some(err) = RsRef::<U>::validate_script(&self.owner) {
+            return Err(err);
+        }
 
-        let Some(script) = script else {
-            return Some(GodotScriptCastError::NoScriptAttached);
+        let owner = self
+            .owner
+            .upcast::<Object>()
+            .try_cast::<U::Base>()
+            .map_err(|object| {
+                GodotScriptCastError::BaseMismatch(
+                    <U::Base as GodotClass>::class_name().to_string(),
+                    object.get_class().to_string(),
+                )
+            })?;
+
+        Ok(RsRef::new(owner))
+    }
+
+    /// Creates a reference that does not keep the object alive.
+    pub fn downgrade(&self) -> WeakRsRef<T> {
+        WeakRsRef {
+            instance_id: self.owner.instance_id(),
+            script_ty: PhantomData,
+        }
+    }
+
+    /// Collects script references into a typed array of their base objects.
+    pub fn to_array(refs: &[Self]) -> Array<Gd<T::Base>> {
+        refs.iter().map(|item| item.owner.clone()).collect()
+    }
+
+    /// Calls a method of the script without panicking. Unknown methods, mismatched argument counts and errors of the
+    /// engine call are reported as [`GodotScriptCallError`] instead.
+    pub fn try_call(
+        &self,
+        method: &str,
+        args: &[Variant],
+    ) -> Result<Variant, GodotScriptCallError> {
+        let arg_count = crate::runtime::script_method_argument_count(T::CLASS_NAME, method)
+            .ok_or_else(|| GodotScriptCallError::InvalidMethod(T::CLASS_NAME, method.to_owned()))?;
+
+        if args.len() < *arg_count.start() {
+            return Err(GodotScriptCallError::TooFewArguments(
+                method.to_owned(),
+                *arg_count.start(),
+                args.len(),
+            ));
+        }
+
+        if args.len() > *arg_count.end() {
+            return Err(GodotScriptCallError::TooManyArguments(
+                method.to_owned(),
+                *arg_count.end(),
+                args.len(),
+            ));
+        }
+
+        let result = self
+            .owner
+            .clone()
+            .upcast::<Object>()
+            .try_call(method, args)?;
+
+        Ok(result)
+    }
+
+    fn validate_script<O: Inherits<Object>>(owner: &Gd<O>) -> Option<GodotScriptCastError> {
+        let class_name = match attached_script_class(owner.upcast_ref()) {
+            Ok(class_name) => class_name,
+            Err(err) => return Some(err),
         };
 
-        let class_name = script.bind().str_class_name();
+        (class_name != T::CLASS_NAME)
+            .then(|| GodotScriptCastError::ClassMismatch(T::CLASS_NAME, class_name))
+    }
+}
 
-        (class_name != T::CLASS_NAME).then(|| {
-            GodotScriptCastError::ClassMismatch(T::CLASS_NAME, script.get_class().to_string())
-        })
+impl<T: GodotScript + 'static> RsRef<T> {
+    /// Borrows the script data of the object, so rust code can access the script without calling it through the
+    /// engine. Fails while the script is borrowed mutably, e.g. by a script method that is still running.
+    pub fn bind(&self) -> Result<ScriptRef<'_, T>, GodotScriptBindError> {
+        ScriptRef::new(self.script_instance_data()?)
+    }
+
+    /// Mutably borrows the script data of the object. Fails while the script is borrowed, e.g. by a script method that
+    /// is still running.
+    pub fn bind_mut(&mut self) -> Result<ScriptMut<'_, T>, GodotScriptBindError> {
+        ScriptMut::new(self.script_instance_data()?)
+    }
+
+    fn script_instance_data(&self) -> Result<InstanceDataCell, GodotScriptBindError> {
+        if let Some(err) = Self::validate_script(&self.owner) {
+            return Err(err.into());
+        }
+
+        crate::runtime::script_instance_data(self.owner.instance_id())
+            .ok_or(GodotScriptBindError::NoInstance)
     }
 }
 
@@ -101,6 +259,24 @@ impl<T: GodotScript> DerefMut for RsRef<T> {
     }
 }
 
+impl<T: GodotScript> GodotConvert for RsRef<T> {
+    type Via = Gd<T::Base>;
+}
+
+impl<T: GodotScript> ToGodot for RsRef<T> {
+    type ToVia<'v> = Gd<T::Base>;
+
+    fn to_godot(&self) -> Self::ToVia<'_> {
+        self.owner.clone()
+    }
+}
+
+impl<T: GodotScript> FromGodot for RsRef<T> {
+    fn try_from_godot(via: Self::Via) -> Result<Self, ConvertError> {
+        via.try_into_script().map_err(ConvertError::with_error)
+    }
+}
+
 impl<T: GodotScript> Clone for RsRef<T> {
     fn clone(&self) -> Self {
         Self {
@@ -110,7 +286,52 @@ impl<T: GodotScript> Clone for RsRef<T> {
     }
 }
 
+/// Script references are equal if they point to the same object.
+impl<T: GodotScript> PartialEq for RsRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.instance_id() == other.instance_id()
+    }
+}
+
+impl<T: GodotScript> Eq for RsRef<T> {}
+
+impl<T: GodotScript> Hash for RsRef<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.instance_id().hash(state);
+    }
+}
+
+/// Reference to a script object that does not keep it alive. Created with [`RsRef::downgrade`].
+#[derive(Debug)]
+pub struct WeakRsRef<T: GodotScript> {
+    instance_id: InstanceId,
+    script_ty: PhantomData<T>,
+}
+
+impl<T: GodotScript> WeakRsRef<T> {
+    pub fn instance_id(&self) -> InstanceId {
+        self.instance_id
+    }
+
+    /// Returns a strong reference if the object is still alive and still has the script `T` attached.
+    pub fn upgrade(&self) -> Option<RsRef<T>> {
+        Gd::<T::Base>::try_from_instance_id(self.instance_id)
+            .ok()?
+            .try_into_script()
+            .ok()
+    }
+}
+
+impl<T: GodotScript> Clone for WeakRsRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: GodotScript> Copy for WeakRsRef<T> {}
+
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum GodotScriptCastError {
     #[error("Object has no script attached!")]
     NoScriptAttached,
@@ -122,6 +343,63 @@ pub enum GodotScriptCastError {
         "Script attached to object does not match expected script class `{0}` but found `{1}`!"
     )]
     ClassMismatch(&'static str, String),
+
+    #[error("Object of class `{1}` does not inherit the script base class `{0}`!")]
+    BaseMismatch(String, String),
+}
+
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum GodotScriptCallError {
+    #[error("Script class `{0}` has no method `{1}`!")]
+    InvalidMethod(&'static str, String),
+
+    #[error("Method `{0}` expects at least {1} arguments but received {2}!")]
+    TooFewArguments(String, usize, usize),
+
+    #[error("Method `{0}` expects at most {1} arguments but received {2}!")]
+    TooManyArguments(String, usize, usize),
+
+    #[error(transparent)]
+    Call(#[from] CallError),
+}
+
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum GodotScriptBindError {
+    #[error(transparent)]
+    Cast(#[from] GodotScriptCastError),
+
+    #[error("Script of the object has not been instantiated!")]
+    NoInstance,
+
+    #[error("Script instance is already borrowed!")]
+    AlreadyBorrowed,
+
+    #[error("Script instance does not contain the script type `{0}`!")]
+    TypeMismatch(&'static str),
+}
+
+/// Class name of the rust script that is attached to the object.
+fn attached_script_class(object: &Gd<Object>) -> Result<String, GodotScriptCastError> {
+    let script = object
+        .get_script()
+        .try_to::<Option<Gd<crate::runtime::RustScript>>>()
+        .map_err(|_| GodotScriptCastError::NotRustScript)?
+        .ok_or(GodotScriptCastError::NoScriptAttached)?;
+
+    Ok(script.bind().str_class_name())
+}
+
+/// Checks if the object with the given instance id is still alive and has the rust script `class_name` attached.
+///
+/// This is intended for diagnosing the lifecycle of script instances, e.g. after a hot reload.
+pub fn script_instance_exists(class_name: &str, instance_id: InstanceId) -> bool {
+    let Ok(object) = Gd::<Object>::try_from_instance_id(instance_id) else {
+        return false;
+    };
+
+    attached_script_class(&object).is_ok_and(|attached| attached == class_name)
 }
 
 pub trait CastToScript<T: GodotScript> {
@@ -171,10 +449,23 @@ impl<T: GodotScript, B: Inherits<T::Base> + Inherits<Object>> CastToScript<T> fo
     }
 }
 
+/// Non-panicking check for the rust script of an object.
+///
+/// Unlike [`CastToScript`], the base of the script does not have to be known to be compatible with the object type.
+pub trait IsScript {
+    /// Returns `true` if the rust script `T` is attached to the object.
+    fn is_script<T: GodotScript>(&self) -> bool;
+}
+
+impl<B: Inherits<Object>> IsScript for Gd<B> {
+    fn is_script<T: GodotScript>(&self) -> bool {
+        RsRef::<T>::validate_script(self).is_none()
+    }
+}
+
 #[macro_export]
 macro_rules! define_script_root {
     () => {
-        #[no_mangle]
         pub fn __godot_rust_script_init(
         ) -> ::std::vec::Vec<$crate::private_export::RustScriptMetaData> {
             use $crate::godot::obj::EngineEnum;
@@ -184,7 +475,7 @@ macro_rules! define_script_root {
                 .lock()
                 .expect("unable to aquire mutex lock");
 
-            $crate::private_export::assemble_metadata(lock.iter())
+            $crate::private_export::assemble_metadata(lock.iter(), module_path!())
         }
 
         pub const __GODOT_RUST_SCRIPT_SRC_ROOT: &str = $crate::private_export::concat!(
@@ -210,16 +501,56 @@ macro_rules! setup_library {
     };
 }
 
-pub trait GodotScriptEnum: GodotConvert + FromGodot + ToGodot {}
+pub trait GodotScriptEnum: GodotConvert + FromGodot + ToGodot {
+    const VARIANTS: &'static [ScriptEnumVariant];
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScriptEnumVariant {
+    pub name: &'static str,
+    pub value: i64,
+    pub description: &'static str,
+}
 
 #[macro_export]
 macro_rules! init {
+    ([$($scripts_module:tt),+ $(,)?]) => {
+        $crate::RustScriptExtensionLayer::initialize_roots(&[
+            $($crate::ScriptRoot {
+                init: $scripts_module::__godot_rust_script_init,
+                src_dir: $scripts_module::__GODOT_RUST_SCRIPT_SRC_ROOT,
+            }),+
+        ])
+    };
+
+    ([$($scripts_module:tt),+ $(,)?], $level:expr, $target:expr) => {
+        $crate::RustScriptExtensionLayer::initialize_roots_at(
+            $level,
+            $target,
+            &[
+                $($crate::ScriptRoot {
+                    init: $scripts_module::__godot_rust_script_init,
+                    src_dir: $scripts_module::__GODOT_RUST_SCRIPT_SRC_ROOT,
+                }),+
+            ],
+        )
+    };
+
     ($scripts_module:tt) => {
         $crate::RustScriptExtensionLayer::initialize(
             $scripts_module::__godot_rust_script_init,
             $scripts_module::__GODOT_RUST_SCRIPT_SRC_ROOT,
         )
     };
+
+    ($scripts_module:tt, $level:expr, $target:expr) => {
+        $crate::RustScriptExtensionLayer::initialize_at(
+            $level,
+            $target,
+            $scripts_module::__godot_rust_script_init,
+            $scripts_module::__GODOT_RUST_SCRIPT_SRC_ROOT,
+        )
+    };
 }
 
 #[macro_export]
@@ -227,4 +558,8 @@ macro_rules! deinit {
     () => {
         $crate::RustScriptExtensionLayer::deinitialize()
     };
+
+    ($level:expr, $target:expr) => {
+        $crate::RustScriptExtensionLayer::deinitialize_at($level, $target)
+    };
 }
@@ -13,19 +13,45 @@ use godot::builtin::{
     Rect2, Rect2i, Rid, StringName, Transform2D, Transform3D, Vector2, Vector2i, Vector3, Vector3i,
     Vector4, Vector4i,
 };
-use godot::classes::{Node, Resource};
+use godot::classes::{Node, PackedScene, Resource};
 use godot::global::PropertyHint;
-use godot::meta::{ArrayElement, FromGodot, GodotConvert, GodotType, ToGodot};
+use godot::meta::{ArrayElement, ClassName, FromGodot, GodotConvert, GodotType, ToGodot};
 use godot::obj::{EngineEnum, Gd};
 use godot::prelude::GodotClass;
 use godot::sys::GodotFfi;
 
+use super::{GodotScript, RsRef};
+
 pub trait GodotScriptExport: GodotConvert + FromGodot + ToGodot {
     fn hint_string(custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String;
 
     fn hint(custom: Option<PropertyHint>) -> PropertyHint;
+
+    /// Whether the value has to be assigned in the editor. Required properties have no default value.
+    const REQUIRED: bool = false;
+
+    /// Whether a required value has been assigned. Unassigned values are reported by
+    /// [`GodotScript::missing_required_exports`].
+    fn is_assigned(&self) -> bool {
+        true
+    }
+
+    /// Class name that is reported to the engine for properties, arguments and return values of this type.
+    fn class_name() -> ClassName {
+        <<Self as GodotConvert>::Via as GodotType>::class_name()
+    }
 }
 
+/// Exported types that can represent a property that has been cleared in the inspector.
+///
+/// The inspector clears object properties by assigning `null`, which a bare `Gd<T>` or `RsRef<T>` can not hold. Custom
+/// [`GodotScriptExport`] types have to implement this trait as well to be exported.
+#[diagnostic::on_unimplemented(
+    message = "exported `{Self}` fields can not be cleared in the editor",
+    note = "use `Option<{Self}>` or `OnEditor<{Self}>` instead"
+)]
+pub trait ClearableExport {}
+
 impl<T: GodotClass> GodotScriptExport for Gd<T> {
     fn hint_string(_custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String {
         if let Some(custom) = custom_string {
@@ -50,6 +76,28 @@ impl<T: GodotClass> GodotScriptExport for Gd<T> {
     }
 }
 
+impl<T: GodotScript> GodotScriptExport for RsRef<T> {
+    fn hint_string(_custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String {
+        if let Some(custom) = custom_string {
+            return custom;
+        }
+
+        T::CLASS_NAME.to_string()
+    }
+
+    fn hint(custom: Option<PropertyHint>) -> PropertyHint {
+        if let Some(custom) = custom {
+            return custom;
+        }
+
+        <Gd<T::Base> as GodotScriptExport>::hint(None)
+    }
+
+    fn class_name() -> ClassName {
+        T::class_name()
+    }
+}
+
 impl<T: GodotScriptExport> GodotScriptExport for Option<T>
 where
     for<'v> T: 'v,
@@ -65,8 +113,14 @@ where
     fn hint(custom: Option<PropertyHint>) -> PropertyHint {
         T::hint(custom)
     }
+
+    fn class_name() -> ClassName {
+        T::class_name()
+    }
 }
 
+impl<T> ClearableExport for Option<T> {}
+
 impl<T: ArrayElement + GodotScriptExport + GodotType> GodotScriptExport for Array<T> {
     fn hint_string(custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String {
         let element_type = <<T as GodotType>::Ffi as GodotFfi>::variant_type().ord();
@@ -85,6 +139,8 @@ impl<T: ArrayElement + GodotScriptExport + GodotType> GodotScriptExport for Arra
     }
 }
 
+impl<T: ArrayElement> ClearableExport for Array<T> {}
+
 macro_rules! default_export {
     ($ty:ty) => {
         impl GodotScriptExport for $ty {
@@ -107,6 +163,8 @@ macro_rules! default_export {
                 PropertyHint::NONE
             }
         }
+
+        impl ClearableExport for $ty {}
     };
 }
 
@@ -172,3 +230,32 @@ default_export!(godot::builtin::Signal);
 default_export!(Dictionary);
 
 default_export!(Rid);
+
+/// Field types that can be exported with `#[export(color_no_alpha)]`.
+#[diagnostic::on_unimplemented(message = "color_no_alpha can only be used on Color fields")]
+pub trait ColorNoAlphaExport {}
+
+impl ColorNoAlphaExport for Color {}
+
+/// Field types that can be exported with `#[export(exp_easing = [...])]`.
+#[diagnostic::on_unimplemented(message = "exp_easing can only be used on f32 or f64 fields")]
+pub trait ExpEasingExport {}
+
+impl ExpEasingExport for f32 {}
+impl ExpEasingExport for f64 {}
+
+/// Field types that can be exported with `#[export(multiline)]`.
+#[diagnostic::on_unimplemented(message = "multiline can only be used on GString or String fields")]
+pub trait MultilineExport {}
+
+impl MultilineExport for GString {}
+impl MultilineExport for String {}
+
+/// Field types that can be exported with `#[export(scene_root = "...")]`.
+#[diagnostic::on_unimplemented(
+    message = "scene_root can only be used on Gd<PackedScene> or Option<Gd<PackedScene>> fields"
+)]
+pub trait SceneRootExport {}
+
+impl SceneRootExport for Gd<PackedScene> {}
+impl SceneRootExport for Option<Gd<PackedScene>> {}
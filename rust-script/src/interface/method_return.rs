@@ -0,0 +1,85 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Display;
+
+use godot::builtin::{Variant, VariantType};
+use godot::meta::{FromGodot, GodotConvert, GodotType, ToGodot};
+use godot::sys::GodotFfi;
+
+use super::GodotScriptCallError;
+
+/// Marker for script methods that return a plain value.
+pub struct ValueReturn;
+
+/// Marker for fallible script methods that return a `Result`.
+pub struct ResultReturn;
+
+/// Return type of a script method.
+///
+/// Methods either return a value that can be passed to the engine, or a `Result` of such a value. The error of a
+/// fallible method is reported to the engine as a failed call. `Kind` only tells the two implementations apart and is
+/// always inferred.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can not be returned from a script method",
+    note = "script methods have to return a `ToGodot` value or a `Result<T, E>` where `T: ToGodot` and `E: Display`"
+)]
+pub trait ScriptMethodReturn<Kind> {
+    /// Value that is passed to the engine.
+    type Value: ToGodot;
+
+    /// Return type of the method in the generated script interface.
+    type Interface;
+
+    /// Unwraps the value that is passed to the engine, or the message of the error of a fallible method.
+    fn into_value(self) -> Result<Self::Value, String>;
+
+    /// Converts the result of calling the method through the script interface.
+    fn from_call(result: Result<Variant, GodotScriptCallError>) -> Self::Interface
+    where
+        Self::Value: FromGodot;
+
+    fn variant_type() -> VariantType {
+        <<Self::Value as GodotConvert>::Via as GodotType>::Ffi::variant_type()
+    }
+}
+
+impl<T: ToGodot> ScriptMethodReturn<ValueReturn> for T {
+    type Value = T;
+    type Interface = T;
+
+    fn into_value(self) -> Result<Self::Value, String> {
+        Ok(self)
+    }
+
+    fn from_call(result: Result<Variant, GodotScriptCallError>) -> Self::Interface
+    where
+        Self::Value: FromGodot,
+    {
+        match result {
+            Ok(value) => value.to(),
+            Err(err) => panic!("{err}"),
+        }
+    }
+}
+
+impl<T: ToGodot, E: Display> ScriptMethodReturn<ResultReturn> for Result<T, E> {
+    type Value = T;
+
+    // the error of a fallible method does not cross the engine boundary, callers receive the call error instead.
+    type Interface = Result<T, GodotScriptCallError>;
+
+    fn into_value(self) -> Result<Self::Value, String> {
+        self.map_err(|err| err.to_string())
+    }
+
+    fn from_call(result: Result<Variant, GodotScriptCallError>) -> Self::Interface
+    where
+        Self::Value: FromGodot,
+    {
+        result.map(|value| value.to())
+    }
+}
@@ -0,0 +1,118 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::ops::{Deref, DerefMut};
+
+use godot::global::PropertyHint;
+use godot::meta::{ConvertError, FromGodot, GodotConvert, ToGodot};
+use godot::obj::{Gd, GodotClass};
+
+use super::{ClearableExport, GodotScriptExport};
+
+/// Exported value that has to be assigned in the editor.
+///
+/// The field starts out unassigned and dereferencing it before a value has been set panics. Use [`OnEditor::is_init`]
+/// or [`OnEditor::get`] to handle a missing value gracefully.
+#[derive(Debug, Clone)]
+pub struct OnEditor<T> {
+    value: ValueState<T>,
+}
+
+#[derive(Debug, Clone)]
+enum ValueState<T> {
+    Invalid,
+    Initialized(T),
+}
+
+impl<T> OnEditor<T> {
+    /// Whether a value has been assigned.
+    pub fn is_init(&self) -> bool {
+        matches!(self.value, ValueState::Initialized(_))
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        match &self.value {
+            ValueState::Initialized(value) => Some(value),
+            ValueState::Invalid => None,
+        }
+    }
+
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        match &mut self.value {
+            ValueState::Initialized(value) => Some(value),
+            ValueState::Invalid => None,
+        }
+    }
+}
+
+impl<T> Default for OnEditor<T> {
+    fn default() -> Self {
+        Self {
+            value: ValueState::Invalid,
+        }
+    }
+}
+
+impl<T> From<T> for OnEditor<T> {
+    fn from(value: T) -> Self {
+        Self {
+            value: ValueState::Initialized(value),
+        }
+    }
+}
+
+impl<T> Deref for OnEditor<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+            .expect("OnEditor value has not been assigned in the editor")
+    }
+}
+
+impl<T> DerefMut for OnEditor<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+            .expect("OnEditor value has not been assigned in the editor")
+    }
+}
+
+impl<T: GodotClass> GodotConvert for OnEditor<Gd<T>> {
+    type Via = Option<Gd<T>>;
+}
+
+impl<T: GodotClass> ToGodot for OnEditor<Gd<T>> {
+    type ToVia<'v> = Self::Via;
+
+    fn to_godot(&self) -> Self::Via {
+        self.get().cloned()
+    }
+}
+
+/// `nil` resets the value to the unassigned state.
+impl<T: GodotClass> FromGodot for OnEditor<Gd<T>> {
+    fn try_from_godot(via: Self::Via) -> Result<Self, ConvertError> {
+        Ok(via.map(Self::from).unwrap_or_default())
+    }
+}
+
+impl<T: GodotClass> GodotScriptExport for OnEditor<Gd<T>> {
+    const REQUIRED: bool = true;
+
+    fn hint_string(custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String {
+        <Gd<T> as GodotScriptExport>::hint_string(custom_hint, custom_string)
+    }
+
+    fn hint(custom: Option<PropertyHint>) -> PropertyHint {
+        <Gd<T> as GodotScriptExport>::hint(custom)
+    }
+
+    fn is_assigned(&self) -> bool {
+        self.is_init()
+    }
+}
+
+impl<T: GodotClass> ClearableExport for OnEditor<Gd<T>> {}
@@ -0,0 +1,125 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::ops::{Deref, DerefMut};
+
+use godot::builtin::{Array, VariantType};
+use godot::global::PropertyHint;
+use godot::meta::{ConvertError, FromGodot, GodotConvert, ToGodot};
+use godot::obj::{EngineEnum, Gd};
+
+use super::{ClearableExport, GodotScript, GodotScriptExport, RsRef};
+
+/// Array of script references.
+///
+/// Godot arrays can only hold engine types, so `Array<RsRef<T>>` is not possible. This array is passed to the engine
+/// as an array of the script base objects and is exported with the script class of `T` for every element.
+#[derive(Debug)]
+pub struct RsRefArray<T: GodotScript>(Vec<RsRef<T>>);
+
+impl<T: GodotScript> RsRefArray<T> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn into_vec(self) -> Vec<RsRef<T>> {
+        self.0
+    }
+}
+
+impl<T: GodotScript> Clone for RsRefArray<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: GodotScript> PartialEq for RsRefArray<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: GodotScript> Eq for RsRefArray<T> {}
+
+impl<T: GodotScript> Default for RsRefArray<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: GodotScript> Deref for RsRefArray<T> {
+    type Target = Vec<RsRef<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: GodotScript> DerefMut for RsRefArray<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: GodotScript> From<Vec<RsRef<T>>> for RsRefArray<T> {
+    fn from(value: Vec<RsRef<T>>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: GodotScript> FromIterator<RsRef<T>> for RsRefArray<T> {
+    fn from_iter<I: IntoIterator<Item = RsRef<T>>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T: GodotScript> GodotConvert for RsRefArray<T> {
+    type Via = Array<Gd<T::Base>>;
+}
+
+impl<T: GodotScript> ToGodot for RsRefArray<T> {
+    type ToVia<'v>
+        = Self::Via
+    where
+        Self: 'v;
+
+    fn to_godot(&self) -> Self::Via {
+        RsRef::to_array(&self.0)
+    }
+}
+
+impl<T: GodotScript> FromGodot for RsRefArray<T> {
+    fn try_from_godot(via: Self::Via) -> Result<Self, ConvertError> {
+        RsRef::try_from_array(&via)
+            .map(Self)
+            .map_err(ConvertError::with_error)
+    }
+}
+
+impl<T: GodotScript> GodotScriptExport for RsRefArray<T> {
+    fn hint_string(custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String {
+        let element_hint = <RsRef<T> as GodotScriptExport>::hint(custom_hint).ord();
+        let element_hint_string =
+            <RsRef<T> as GodotScriptExport>::hint_string(custom_hint, custom_string);
+
+        format!(
+            "{}/{}:{}",
+            VariantType::OBJECT.ord(),
+            element_hint,
+            element_hint_string
+        )
+    }
+
+    fn hint(custom: Option<PropertyHint>) -> PropertyHint {
+        if let Some(custom) = custom {
+            return custom;
+        }
+
+        PropertyHint::ARRAY_TYPE
+    }
+}
+
+impl<T: GodotScript> ClearableExport for RsRefArray<T> {}
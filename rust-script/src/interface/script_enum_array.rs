@@ -0,0 +1,120 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::ops::{Deref, DerefMut};
+
+use godot::builtin::{Array, VariantType};
+use godot::global::PropertyHint;
+use godot::meta::{ConvertError, FromGodot, GodotConvert, ToGodot};
+use godot::obj::EngineEnum;
+
+use super::{ClearableExport, GodotScriptEnum, GodotScriptExport};
+
+/// Array of script enum values.
+///
+/// Godot arrays can only hold engine types, so `Array<ScriptEnum>` is not possible. This array is passed to the engine
+/// as an integer array and is exported with the enum options of `E` for every element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptEnumArray<E>(Vec<E>);
+
+impl<E> ScriptEnumArray<E> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn into_vec(self) -> Vec<E> {
+        self.0
+    }
+}
+
+impl<E> Default for ScriptEnumArray<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Deref for ScriptEnumArray<E> {
+    type Target = Vec<E>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<E> DerefMut for ScriptEnumArray<E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<E> From<Vec<E>> for ScriptEnumArray<E> {
+    fn from(value: Vec<E>) -> Self {
+        Self(value)
+    }
+}
+
+impl<E> FromIterator<E> for ScriptEnumArray<E> {
+    fn from_iter<I: IntoIterator<Item = E>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<E: GodotScriptEnum + GodotConvert<Via = u8>> GodotConvert for ScriptEnumArray<E> {
+    type Via = Array<u8>;
+}
+
+impl<E> ToGodot for ScriptEnumArray<E>
+where
+    E: GodotScriptEnum + GodotConvert<Via = u8>,
+    for<'e> u8: From<&'e E>,
+{
+    type ToVia<'v>
+        = Self::Via
+    where
+        Self: 'v;
+
+    fn to_godot(&self) -> Self::Via {
+        self.0.iter().map(u8::from).collect()
+    }
+}
+
+impl<E> FromGodot for ScriptEnumArray<E>
+where
+    E: GodotScriptEnum + GodotConvert<Via = u8>,
+    for<'e> u8: From<&'e E>,
+{
+    fn try_from_godot(via: Self::Via) -> Result<Self, ConvertError> {
+        via.iter_shared().map(E::try_from_godot).collect()
+    }
+}
+
+impl<E> GodotScriptExport for ScriptEnumArray<E>
+where
+    E: GodotScriptEnum + GodotScriptExport + GodotConvert<Via = u8>,
+    for<'e> u8: From<&'e E>,
+{
+    fn hint_string(custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String {
+        let element_hint = <E as GodotScriptExport>::hint(custom_hint).ord();
+        let element_hint_string = <E as GodotScriptExport>::hint_string(custom_hint, custom_string);
+
+        format!(
+            "{}/{}:{}",
+            VariantType::INT.ord(),
+            element_hint,
+            element_hint_string
+        )
+    }
+
+    fn hint(custom: Option<PropertyHint>) -> PropertyHint {
+        if let Some(custom) = custom {
+            return custom;
+        }
+
+        PropertyHint::ARRAY_TYPE
+    }
+}
+
+impl<E> ClearableExport for ScriptEnumArray<E> {}
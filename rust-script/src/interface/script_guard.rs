@@ -0,0 +1,127 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use godot_cell::blocking::{MutGuard, RefGuard};
+
+use super::{GodotScript, GodotScriptBindError};
+use crate::runtime::{GodotScriptObject, InstanceDataCell};
+
+/// Extends the lifetime of a reference to the data cell to the lifetime of a guard.
+///
+/// # Safety
+/// The returned reference must not outlive `cell`. The guards store their borrow in a field that is declared before
+/// their clone of the cell, so the borrow is released before the clone is dropped. The cell is reference counted, so
+/// it stays at the same address even if the object or its script instance is freed while a guard is alive.
+unsafe fn extend_cell_lifetime<'a>(cell: &InstanceDataCell) -> &'a InstanceDataCell {
+    unsafe { &*(cell as *const InstanceDataCell) }
+}
+
+/// Shared borrow of the script data of an object. Created with [`RsRef::bind`](super::RsRef::bind).
+///
+/// The guard keeps the script data alive, so it stays valid if the object is freed or its script is replaced while the
+/// guard exists.
+pub struct ScriptRef<'a, T: GodotScript> {
+    // declared before `_cell`, so the borrow is released first.
+    guard: RefGuard<'a, Box<dyn GodotScriptObject>>,
+    _cell: InstanceDataCell,
+    script_ty: PhantomData<T>,
+}
+
+impl<'a, T: GodotScript + 'static> ScriptRef<'a, T> {
+    pub(super) fn new(cell: InstanceDataCell) -> Result<Self, GodotScriptBindError> {
+        // SAFETY: the guard is stored next to the cell and dropped before it.
+        let guard = unsafe { extend_cell_lifetime(&cell) }
+            .borrow()
+            .map_err(|_| GodotScriptBindError::AlreadyBorrowed)?;
+
+        if !guard.as_any().is::<T>() {
+            return Err(GodotScriptBindError::TypeMismatch(T::CLASS_NAME));
+        }
+
+        Ok(Self {
+            guard,
+            _cell: cell,
+            script_ty: PhantomData,
+        })
+    }
+}
+
+impl<T: GodotScript + 'static> Deref for ScriptRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard
+            .as_any()
+            .downcast_ref()
+            .expect("script type is checked when the guard is created")
+    }
+}
+
+impl<T: GodotScript + 'static> Debug for ScriptRef<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+/// Exclusive borrow of the script data of an object. Created with [`RsRef::bind_mut`](super::RsRef::bind_mut).
+///
+/// The guard keeps the script data alive, so it stays valid if the object is freed or its script is replaced while the
+/// guard exists.
+pub struct ScriptMut<'a, T: GodotScript> {
+    // declared before `_cell`, so the borrow is released first.
+    guard: MutGuard<'a, Box<dyn GodotScriptObject>>,
+    _cell: InstanceDataCell,
+    script_ty: PhantomData<T>,
+}
+
+impl<'a, T: GodotScript + 'static> ScriptMut<'a, T> {
+    pub(super) fn new(cell: InstanceDataCell) -> Result<Self, GodotScriptBindError> {
+        // SAFETY: the guard is stored next to the cell and dropped before it.
+        let guard = unsafe { extend_cell_lifetime(&cell) }
+            .borrow_mut()
+            .map_err(|_| GodotScriptBindError::AlreadyBorrowed)?;
+
+        if !guard.as_any().is::<T>() {
+            return Err(GodotScriptBindError::TypeMismatch(T::CLASS_NAME));
+        }
+
+        Ok(Self {
+            guard,
+            _cell: cell,
+            script_ty: PhantomData,
+        })
+    }
+}
+
+impl<T: GodotScript + 'static> Deref for ScriptMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard
+            .as_any()
+            .downcast_ref()
+            .expect("script type is checked when the guard is created")
+    }
+}
+
+impl<T: GodotScript + 'static> DerefMut for ScriptMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard
+            .as_any_mut()
+            .downcast_mut()
+            .expect("script type is checked when the guard is created")
+    }
+}
+
+impl<T: GodotScript + 'static> Debug for ScriptMut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
@@ -6,13 +6,20 @@
 
 use std::marker::PhantomData;
 
+#[cfg(since_api = "4.3")]
+use godot::builtin::PackedVector4Array;
 use godot::builtin::{
-    Callable, Dictionary, GString, NodePath, StringName, Variant, Vector2, Vector3,
+    Aabb, Basis, Callable, Color, Dictionary, GString, NodePath, PackedByteArray, PackedColorArray,
+    PackedFloat32Array, PackedFloat64Array, PackedInt32Array, PackedInt64Array, PackedStringArray,
+    PackedVector2Array, PackedVector3Array, Plane, Projection, Quaternion, Rect2, Rect2i, Rid,
+    StringName, Transform2D, Transform3D, Variant, VariantArray, Vector2, Vector2i, Vector3,
+    Vector3i, Vector4, Vector4i,
 };
+use godot::classes::object::ConnectFlags;
 use godot::classes::Object;
-use godot::global::{Error, PropertyHint};
-use godot::meta::{GodotConvert, GodotType, ToGodot};
-use godot::obj::Gd;
+use godot::global::{Error, PropertyHint, PropertyUsageFlags};
+use godot::meta::{FromGodot, GodotConvert, GodotType, ToGodot};
+use godot::obj::{EngineBitfield, Gd};
 
 use crate::static_script_registry::RustScriptPropDesc;
 
@@ -23,8 +30,29 @@ pub trait ScriptSignal {
 
     fn emit(&self, args: Self::Args);
 
+    /// Emits the signal during idle time of the current frame, similar to `emit_signal.call_deferred()` in GDScript.
+    fn emit_deferred(&self, args: Self::Args);
+
+    /// Calls a single callable with the arguments of the signal, without emitting the signal to its connections.
+    fn emit_to(&self, target: &Callable, args: Self::Args) -> Variant;
+
     fn connect(&mut self, callable: Callable) -> Result<(), Error>;
 
+    /// Connects a callable to the signal with the given [`ConnectFlags`], e.g. [`ConnectFlags::ONE_SHOT`].
+    fn connect_flags(&mut self, callable: Callable, flags: ConnectFlags) -> Result<(), Error>;
+
+    fn disconnect(&mut self, callable: &Callable);
+
+    fn is_connected(&self, callable: &Callable) -> bool;
+
+    /// Connects a rust closure to the signal. Emissions with arguments that can not be converted into
+    /// [`ScriptSignal::Args`] are rejected with a call error.
+    #[cfg(since_api = "4.2")]
+    fn connect_fn<F>(&mut self, function: F) -> Result<(), Error>
+    where
+        F: FnMut(Self::Args) + Send + Sync + 'static,
+        Self::Args: 'static;
+
     fn argument_desc() -> Box<[RustScriptPropDesc]>;
 
     fn name(&self) -> &str;
@@ -35,6 +63,10 @@ pub trait SignalArguments {
 
     fn to_variants(&self) -> Vec<Variant>;
 
+    fn from_variants(args: &[&Variant]) -> Option<Self>
+    where
+        Self: Sized;
+
     fn argument_desc() -> Box<[RustScriptPropDesc]>;
 }
 
@@ -47,6 +79,10 @@ impl SignalArguments for () {
         vec![]
     }
 
+    fn from_variants(args: &[&Variant]) -> Option<Self> {
+        args.is_empty().then_some(())
+    }
+
     fn argument_desc() -> Box<[RustScriptPropDesc]> {
         Box::new([])
     }
@@ -59,7 +95,7 @@ macro_rules! count_tts {
 
 macro_rules! tuple_args {
     (impl $($arg: ident),+) => {
-        impl<$($arg: ToGodot),+> SignalArguments for ($($arg,)+) {
+        impl<$($arg: ToGodot + FromGodot),+> SignalArguments for ($($arg,)+) {
             fn count() -> u8 {
                 count_tts!($($arg)+)
             }
@@ -73,6 +109,16 @@ macro_rules! tuple_args {
                 ]
             }
 
+            fn from_variants(args: &[&Variant]) -> Option<Self> {
+                if args.len() != Self::count() as usize {
+                    return None;
+                }
+
+                let mut args = args.iter();
+
+                Some(($(args.next()?.try_to::<$arg>().ok()?,)+))
+            }
+
             fn argument_desc() -> Box<[RustScriptPropDesc]> {
                 Box::new([
                     $(signal_argument_desc!("0", $arg)),+
@@ -106,6 +152,13 @@ macro_rules! single_args {
                 vec![self.to_variant()]
             }
 
+            fn from_variants(args: &[&Variant]) -> Option<Self> {
+                match args {
+                    [arg] => arg.try_to().ok(),
+                    _ => None,
+                }
+            }
+
             fn argument_desc() -> Box<[RustScriptPropDesc]> {
                 Box::new([
                     signal_argument_desc!("0", $arg),
@@ -126,6 +179,7 @@ macro_rules! signal_argument_desc {
             ty: <<<$type as GodotConvert>::Via as GodotType>::Ffi as godot::sys::GodotFfi>::variant_type(),
             class_name: <<$type as GodotConvert>::Via as GodotType>::class_name(),
             exported: false,
+            usage: PropertyUsageFlags::NONE,
             hint: PropertyHint::NONE,
             hint_string: String::new(),
             description: "",
@@ -135,10 +189,52 @@ macro_rules! signal_argument_desc {
 
 tuple_args!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
 single_args!(
-    bool, u8, u16, u32, u64, i8, i16, i32, i64, f64, GString, StringName, NodePath, Vector2,
-    Vector3, Dictionary
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    i8,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    GString,
+    StringName,
+    NodePath,
+    Vector2,
+    Vector2i,
+    Vector3,
+    Vector3i,
+    Vector4,
+    Vector4i,
+    Rect2,
+    Rect2i,
+    Transform2D,
+    Transform3D,
+    Plane,
+    Quaternion,
+    Aabb,
+    Basis,
+    Projection,
+    Color,
+    Rid,
+    Dictionary,
+    PackedByteArray,
+    PackedInt32Array,
+    PackedInt64Array,
+    PackedFloat32Array,
+    PackedFloat64Array,
+    PackedStringArray,
+    PackedVector2Array,
+    PackedVector3Array,
+    PackedColorArray
 );
 
+#[cfg(since_api = "4.3")]
+single_args!(PackedVector4Array);
+
 #[derive(Debug)]
 pub struct Signal<T: SignalArguments> {
     host: Gd<Object>,
@@ -163,6 +259,20 @@ impl<T: SignalArguments> ScriptSignal for Signal<T> {
             .emit_signal(self.name, &args.to_variants());
     }
 
+    fn emit_deferred(&self, args: Self::Args) {
+        let call_args: Vec<Variant> = std::iter::once(StringName::from(self.name).to_variant())
+            .chain(args.to_variants())
+            .collect();
+
+        self.host.clone().call_deferred("emit_signal", &call_args);
+    }
+
+    fn emit_to(&self, target: &Callable, args: Self::Args) -> Variant {
+        let args: VariantArray = args.to_variants().into_iter().collect();
+
+        target.callv(&args)
+    }
+
     fn connect(&mut self, callable: Callable) -> Result<(), Error> {
         match self.host.connect(self.name, &callable) {
             Error::OK => Ok(()),
@@ -170,6 +280,43 @@ impl<T: SignalArguments> ScriptSignal for Signal<T> {
         }
     }
 
+    fn connect_flags(&mut self, callable: Callable, flags: ConnectFlags) -> Result<(), Error> {
+        let result = self
+            .host
+            .connect_ex(self.name, &callable)
+            .flags(flags.ord() as u32)
+            .done();
+
+        match result {
+            Error::OK => Ok(()),
+            error => Err(error),
+        }
+    }
+
+    fn disconnect(&mut self, callable: &Callable) {
+        self.host.disconnect(self.name, callable);
+    }
+
+    fn is_connected(&self, callable: &Callable) -> bool {
+        self.host.is_connected(self.name, callable)
+    }
+
+    #[cfg(since_api = "4.2")]
+    fn connect_fn<F>(&mut self, mut function: F) -> Result<(), Error>
+    where
+        F: FnMut(Self::Args) + Send + Sync + 'static,
+        Self::Args: 'static,
+    {
+        let callable = Callable::from_fn(self.name, move |args| {
+            let args = T::from_variants(args).ok_or(())?;
+
+            function(args);
+            Ok(Variant::nil())
+        });
+
+        self.connect(callable)
+    }
+
     fn argument_desc() -> Box<[RustScriptPropDesc]> {
         <T as SignalArguments>::argument_desc()
     }
@@ -0,0 +1,144 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use godot::builtin::Dictionary;
+use godot::global::PropertyHint;
+use godot::meta::{ConvertError, FromGodot, GodotConvert, GodotType, ToGodot};
+use godot::obj::EngineEnum;
+use godot::sys::GodotFfi;
+
+use super::{ClearableExport, GodotScriptExport};
+
+/// Dictionary with typed keys and values.
+///
+/// The dictionary is passed to the engine as a regular [`Dictionary`], but is exported with the key and value types so
+/// the inspector only accepts matching entries.
+pub struct TypedDictionary<K, V> {
+    inner: Dictionary,
+    entry_ty: PhantomData<(K, V)>,
+}
+
+impl<K: ToGodot, V: ToGodot + FromGodot> TypedDictionary<K, V> {
+    pub fn new() -> Self {
+        Self {
+            inner: Dictionary::new(),
+            entry_ty: PhantomData,
+        }
+    }
+
+    /// Value of the given key. Values that can not be converted into `V` are treated as missing.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.inner
+            .get(key.to_variant())
+            .and_then(|value| value.try_to().ok())
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.inner.set(key.to_variant(), value.to_variant());
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.inner
+            .remove(key.to_variant())
+            .and_then(|value| value.try_to().ok())
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner.contains_key(key.to_variant())
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn as_dictionary(&self) -> &Dictionary {
+        &self.inner
+    }
+}
+
+impl<K: ToGodot, V: ToGodot + FromGodot> Default for TypedDictionary<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Clone for TypedDictionary<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            entry_ty: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Debug for TypedDictionary<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TypedDictionary").field(&self.inner).finish()
+    }
+}
+
+impl<K, V> GodotConvert for TypedDictionary<K, V> {
+    type Via = Dictionary;
+}
+
+impl<K, V> ToGodot for TypedDictionary<K, V> {
+    type ToVia<'v>
+        = Self::Via
+    where
+        Self: 'v;
+
+    fn to_godot(&self) -> Self::Via {
+        self.inner.clone()
+    }
+}
+
+impl<K, V> FromGodot for TypedDictionary<K, V> {
+    fn try_from_godot(via: Self::Via) -> Result<Self, ConvertError> {
+        Ok(Self {
+            inner: via,
+            entry_ty: PhantomData,
+        })
+    }
+}
+
+impl<K: GodotScriptExport, V: GodotScriptExport> GodotScriptExport for TypedDictionary<K, V> {
+    fn hint_string(_custom_hint: Option<PropertyHint>, custom_string: Option<String>) -> String {
+        if let Some(custom) = custom_string {
+            return custom;
+        }
+
+        format!("{};{}", entry_hint_string::<K>(), entry_hint_string::<V>())
+    }
+
+    fn hint(custom: Option<PropertyHint>) -> PropertyHint {
+        if let Some(custom) = custom {
+            return custom;
+        }
+
+        PropertyHint::DICTIONARY_TYPE
+    }
+}
+
+impl<K, V> ClearableExport for TypedDictionary<K, V> {}
+
+/// Type description of a key or value in the same format as the element type of array hints.
+fn entry_hint_string<T: GodotScriptExport>() -> String {
+    let variant_type = <<T::Via as GodotType>::Ffi as GodotFfi>::variant_type().ord();
+
+    format!(
+        "{}/{}:{}",
+        variant_type,
+        T::hint(None).ord(),
+        T::hint_string(None, None)
+    )
+}
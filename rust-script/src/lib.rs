@@ -13,14 +13,23 @@ mod static_script_registry;
 
 pub use godot_rust_script_derive::{godot_script_impl, GodotScript, GodotScriptEnum};
 pub use interface::*;
-pub use runtime::RustScriptExtensionLayer;
+pub use runtime::{RustScriptExtensionLayer, ScriptRoot};
 
 #[doc(hidden)]
 pub mod private_export {
+    pub use crate::interface::export::{
+        ClearableExport, ColorNoAlphaExport, ExpEasingExport, MultilineExport, SceneRootExport,
+    };
+    pub use crate::interface::method_return::{ResultReturn, ScriptMethodReturn, ValueReturn};
+    pub use crate::runtime::{
+        script_instance_label, validate_scene_root, with_default_script, ClassNameOf,
+        ConvertClassName, ExportClassName,
+    };
     pub use crate::static_script_registry::{
-        RustScriptMetaData, __godot_rust_plugin_SCRIPT_REGISTRY, assemble_metadata,
-        create_default_data_struct, RegistryItem, RustScriptEntry, RustScriptEntryMethods,
-        RustScriptMethodDesc, RustScriptPropDesc, RustScriptSignalDesc,
+        __godot_rust_plugin_SCRIPT_REGISTRY, assemble_metadata, create_default_data_struct,
+        script_class_name, RegistryItem, RustScriptConstDesc, RustScriptEntry,
+        RustScriptEntryMethods, RustScriptMetaData, RustScriptMethodDesc, RustScriptPropDesc,
+        RustScriptRpcDesc, RustScriptSignalDesc,
     };
     pub use const_str::{concat, replace, strip_prefix, unwrap};
     pub use godot::sys::{plugin_add, plugin_registry};
@@ -4,14 +4,18 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
 use std::{fmt::Debug, marker::PhantomData};
 
-use godot::obj::{script::ScriptBaseMut, Gd};
+use godot::builtin::{NodePath, Signal, Variant};
+use godot::classes::{Node, Object, SceneTree};
+use godot::global::Error;
+use godot::meta::AsArg;
+use godot::obj::{script::ScriptBaseMut, Gd, Inherits};
 use godot::prelude::GodotClass;
 use godot_cell::blocking::GdCell;
 
-use crate::interface::GodotScriptImpl;
+use crate::interface::{CastToScript, GodotScript, GodotScriptImpl, RsRef};
 
 use super::rust_script_instance::{GodotScriptObject, RustScriptInstance};
 
@@ -29,6 +33,16 @@ impl<Script: GodotScriptImpl> Debug for Context<'_, Script> {
 }
 
 impl<Script: GodotScriptImpl> Context<'_, Script> {
+    /// Typed handle to the base object of the script.
+    ///
+    /// The script instance stays borrowed while the handle is used, so it must only be used for engine calls that do
+    /// not call back into the script, e.g. reading a property of the base node. Calling script methods, emitting
+    /// signals that are connected to the script or anything else that re-enters the instance through this handle is
+    /// not allowed. Use [`Context::reentrant_scope`] for these calls.
+    pub fn base(&self) -> Gd<Script::ImplBase> {
+        self.base.deref().clone().cast::<Script::ImplBase>()
+    }
+
     pub fn reentrant_scope<T: GodotScriptObject + 'static, Args, Return>(
         &mut self,
         self_ref: &mut T,
@@ -56,6 +70,90 @@ impl<Script: GodotScriptImpl> Context<'_, Script> {
 
         result
     }
+
+    /// Emits a signal of the base object.
+    ///
+    /// Callables connected to the signal can call back into the script, so the signal is emitted inside a
+    /// [`Context::reentrant_scope`].
+    pub fn emit_signal(&mut self, self_ref: &mut Script, name: &str, args: &[Variant]) -> Error
+    where
+        Script: GodotScriptObject + 'static,
+    {
+        self.reentrant_scope(self_ref, |base: Gd<Script::ImplBase>| {
+            base.upcast::<Object>().emit_signal(name, args)
+        })
+    }
+
+    /// Signal of the base object that a script method can return to make the call awaitable from GDScript, e.g.
+    /// `await node.long_task()`.
+    ///
+    /// The signal is added to the base object as a user signal if it does not exist yet. The script resolves the
+    /// `await` by emitting the signal once the work is done, e.g. from a later `_process` call. Signals must be emitted
+    /// on the main thread, so background threads have to emit it through `call_deferred`. All pending awaits of the
+    /// same signal name are resolved by the same emission.
+    pub fn defer_signal(&mut self, name: &str) -> Signal {
+        let mut base = self.base.deref().clone();
+
+        if !base.has_signal(name) {
+            base.add_user_signal(name);
+        }
+
+        Signal::from_object_signal(&base, name)
+    }
+
+    /// Calls a method of the base object during idle time of the current frame, similar to `call_deferred` in
+    /// GDScript. Script methods called this way do not run while the current script method still borrows the script.
+    pub fn call_deferred(&mut self, self_ref: &mut Script, method: &str, args: &[Variant])
+    where
+        Script: GodotScriptObject + 'static,
+    {
+        self.reentrant_scope(self_ref, |base: Gd<Script::ImplBase>| {
+            base.upcast::<Object>().call_deferred(method, args);
+        })
+    }
+}
+
+impl<Script: GodotScriptImpl> Context<'_, Script>
+where
+    Script::ImplBase: Inherits<Node>,
+{
+    /// The scene tree the node of the script is currently in.
+    pub fn get_tree(&mut self) -> Option<Gd<SceneTree>> {
+        self.base_node().get_tree()
+    }
+
+    /// Looks up a node relative to the node of the script.
+    pub fn get_node(&mut self, path: impl AsArg<NodePath>) -> Option<Gd<Node>> {
+        self.base_node().get_node_or_null(path)
+    }
+
+    /// Looks up a node relative to the node of the script and casts it to the script `T`. Returns `None` if the node
+    /// does not exist or does not have the script attached.
+    ///
+    /// The lookup runs inside a [`Context::reentrant_scope`], so the node can be the node of the script itself.
+    pub fn get_script_node<T: GodotScript>(
+        &mut self,
+        self_ref: &mut Script,
+        path: &NodePath,
+    ) -> Option<RsRef<T>>
+    where
+        Script: GodotScriptObject + 'static,
+        T::Base: Inherits<Node>,
+    {
+        self.reentrant_scope(self_ref, |base: Gd<Script::ImplBase>| {
+            let node = base
+                .upcast::<Node>()
+                .get_node_or_null(path)?
+                .try_cast::<T::Base>()
+                .ok()?;
+
+            CastToScript::<T>::try_into_script(node).ok()
+        })
+    }
+
+    fn base_node(&self) -> Gd<Node> {
+        self.base().upcast()
+    }
 }
 
 pub struct GenericContext<'a> {
@@ -0,0 +1,53 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::marker::PhantomData;
+
+use godot::meta::{ClassName, GodotConvert, GodotType};
+
+use crate::interface::method_return::ScriptMethodReturn;
+use crate::interface::GodotScriptExport;
+
+/// Resolves the class name of a method argument or return value of type `T`.
+///
+/// The generated code calls `(&&ClassNameOf::<T>::default()).class_name()`. Method resolution prefers
+/// [`ExportClassName`], so types that implement [`GodotScriptExport`] describe themselves, e.g. a `RsRef<T>` is
+/// described with the class of its script. All other types fall back to [`ConvertClassName`], the class of their
+/// godot representation.
+pub struct ClassNameOf<T>(PhantomData<T>);
+
+impl<T> ClassNameOf<T> {
+    /// Class name of the value that a method with the return type `R` passes to the engine.
+    pub fn method_return<R: ScriptMethodReturn<K, Value = T>, K>() -> Self {
+        Self::default()
+    }
+}
+
+impl<T> Default for ClassNameOf<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+pub trait ExportClassName {
+    fn class_name(&self) -> ClassName;
+}
+
+impl<T: GodotScriptExport> ExportClassName for &ClassNameOf<T> {
+    fn class_name(&self) -> ClassName {
+        T::class_name()
+    }
+}
+
+pub trait ConvertClassName {
+    fn class_name(&self) -> ClassName;
+}
+
+impl<T: GodotConvert> ConvertClassName for ClassNameOf<T> {
+    fn class_name(&self) -> ClassName {
+        <T::Via as GodotType>::class_name()
+    }
+}
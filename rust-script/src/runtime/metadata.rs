@@ -128,9 +128,11 @@ impl ToMethodDoc for MethodInfo {
 
 impl<T: ToMethodDoc> ToMethodDoc for Documented<T> {
     fn to_method_doc(&self) -> Dictionary {
-        self.inner
-            .to_method_doc()
-            .apply(|dict| dict.set("description", self.description))
+        self.inner.to_method_doc().apply(|dict| {
+            dict.set("description", self.description);
+            dict.set("is_deprecated", self.is_deprecated);
+            dict.set("is_experimental", self.is_experimental);
+        })
     }
 }
 
@@ -138,12 +140,16 @@ impl<T: ToMethodDoc> ToMethodDoc for Documented<T> {
 pub struct Documented<T> {
     inner: T,
     description: &'static str,
+    is_deprecated: bool,
+    is_experimental: bool,
 }
 
 impl From<crate::static_script_registry::RustScriptPropertyInfo> for Documented<PropertyInfo> {
     fn from(value: crate::static_script_registry::RustScriptPropertyInfo) -> Self {
         Self {
             description: value.description,
+            is_deprecated: false,
+            is_experimental: false,
             inner: (&value).into(),
         }
     }
@@ -153,6 +159,8 @@ impl From<crate::static_script_registry::RustScriptMethodInfo> for Documented<Me
     fn from(value: crate::static_script_registry::RustScriptMethodInfo) -> Self {
         Self {
             description: value.description,
+            is_deprecated: value.is_deprecated,
+            is_experimental: value.is_experimental,
             inner: (&value).into(),
         }
     }
@@ -162,6 +170,8 @@ impl From<crate::static_script_registry::RustScriptSignalInfo> for Documented<Me
     fn from(value: crate::static_script_registry::RustScriptSignalInfo) -> Self {
         Self {
             description: value.description,
+            is_deprecated: false,
+            is_experimental: false,
             inner: (&value).into(),
         }
     }
@@ -180,6 +190,8 @@ impl<T: Clone> Clone for Documented<T> {
         Self {
             inner: self.inner.clone(),
             description: self.description,
+            is_deprecated: self.is_deprecated,
+            is_experimental: self.is_experimental,
         }
     }
 }
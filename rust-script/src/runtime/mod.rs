@@ -5,40 +5,62 @@
  */
 
 mod call_context;
+mod class_name;
 mod downgrade_self;
 mod metadata;
+mod profiling;
 mod resource_loader;
 mod resource_saver;
 mod rust_script;
 mod rust_script_instance;
 mod rust_script_language;
+mod scene_root;
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+    time::SystemTime,
+};
 
-use std::{collections::HashMap, sync::RwLock};
-
+use godot::builtin::GString;
 use godot::classes::{
-    Engine, RefCounted, ResourceFormatLoader, ResourceFormatSaver, ResourceLoader, ResourceSaver,
-    ScriptLanguage,
+    ClassDb, Engine, Object, ProjectSettings, RefCounted, ResourceFormatLoader,
+    ResourceFormatSaver, ResourceLoader, ResourceSaver, Script, ScriptLanguage,
 };
-use godot::global::godot_warn;
-use godot::obj::{GodotClass, Inherits};
+use godot::global::{self, godot_error, godot_warn, MethodFlags};
+use godot::init::InitLevel;
+use godot::obj::{EngineBitfield, GodotClass, Inherits, InstanceId};
 use godot::prelude::{godot_print, Gd};
 use godot::register::GodotClass;
+use godot::sys::{self, GodotFfi};
 use once_cell::sync::Lazy;
 
 use crate::runtime::{
     resource_loader::RustScriptResourceLoader, resource_saver::RustScriptResourceSaver,
 };
 use crate::static_script_registry::RustScriptMetaData;
+use crate::GodotScript;
 
 use self::rust_script_language::RustScriptLanguage;
 
 pub use call_context::Context;
+pub use class_name::{ClassNameOf, ConvertClassName, ExportClassName};
 pub(crate) use rust_script::RustScript;
-pub(crate) use rust_script_instance::GodotScriptObject;
+pub(crate) use rust_script_instance::{script_instance_data, GodotScriptObject, InstanceDataCell};
+pub use scene_root::validate_scene_root;
 
-static SCRIPT_REGISTRY: Lazy<RwLock<HashMap<String, RustScriptMetaData>>> =
+static SCRIPT_REGISTRY: Lazy<RwLock<HashMap<String, Arc<RustScriptMetaData>>>> =
     Lazy::new(RwLock::default);
 
+/// Id of the loaded script library. It is derived from the library file and changes when a rebuilt library is
+/// loaded.
+static LIBRARY_LOAD_ID: AtomicU64 = AtomicU64::new(0);
+
 #[derive(GodotClass)]
 #[class(base = Object, init)]
 struct RefCountedSingleton {
@@ -61,23 +83,51 @@ pub trait RustScriptLibInit: Fn() -> Vec<RustScriptMetaData> {}
 
 impl<F> RustScriptLibInit for F where F: Fn() -> Vec<RustScriptMetaData> {}
 
+/// Root module of a set of scripts, declared with [`define_script_root!`](crate::define_script_root). Multiple roots
+/// can be passed to the [`init!`](crate::init) macro.
+#[derive(Debug, Clone, Copy)]
+pub struct ScriptRoot {
+    pub init: fn() -> Vec<RustScriptMetaData>,
+    pub src_dir: &'static str,
+}
+
 pub struct RustScriptExtensionLayer;
 
 impl RustScriptExtensionLayer {
     pub fn initialize<F: RustScriptLibInit + 'static + Clone>(
         lib_init_fn: F,
         scripts_src_dir: &'static str,
+    ) {
+        Self::initialize_runtime(&[&lib_init_fn], vec![scripts_src_dir]);
+    }
+
+    /// Initializes the script runtime with the scripts of multiple script roots.
+    pub fn initialize_roots(roots: &[ScriptRoot]) {
+        let lib_init_fns: Vec<&dyn RustScriptLibInit> = roots
+            .iter()
+            .map(|root| &root.init as &dyn RustScriptLibInit)
+            .collect();
+
+        Self::initialize_runtime(
+            &lib_init_fns,
+            roots.iter().map(|root| root.src_dir).collect(),
+        );
+    }
+
+    fn initialize_runtime(
+        lib_init_fns: &[&dyn RustScriptLibInit],
+        scripts_src_dirs: Vec<&'static str>,
     ) {
         godot_print!("registering rust scripting language...");
 
-        let lang: Gd<RustScriptLanguage> = RustScriptLanguage::new(Some(scripts_src_dir));
+        let lang: Gd<RustScriptLanguage> = RustScriptLanguage::new(scripts_src_dirs);
         let res_loader = RustScriptResourceLoader::new(lang.clone());
         let res_saver = Gd::from_object(RustScriptResourceSaver);
 
         let mut engine = Engine::singleton();
 
         godot_print!("loading rust scripts...");
-        load_rust_scripts(lib_init_fn);
+        load_rust_scripts(lib_init_fns);
 
         engine.register_script_language(&lang);
         engine.register_singleton(&RustScriptLanguage::class_name().to_string_name(), &lang);
@@ -97,6 +147,33 @@ impl RustScriptExtensionLayer {
         godot_print!("finished registering rust scripting language!");
     }
 
+    /// Initializes the script runtime only if the current init `level` matches the `target` level.
+    ///
+    /// This allows registering scripts earlier than [`InitLevel::Scene`], e.g. at [`InitLevel::Servers`]. The runtime
+    /// has to be deinitialized at the same target level via [`Self::deinitialize_at`].
+    pub fn initialize_at<F: RustScriptLibInit + 'static + Clone>(
+        level: InitLevel,
+        target: InitLevel,
+        lib_init_fn: F,
+        scripts_src_dir: &'static str,
+    ) {
+        if level != target {
+            return;
+        }
+
+        Self::initialize(lib_init_fn, scripts_src_dir);
+    }
+
+    /// Initializes the script runtime with multiple script roots only if the current init `level` matches the `target`
+    /// level.
+    pub fn initialize_roots_at(level: InitLevel, target: InitLevel, roots: &[ScriptRoot]) {
+        if level != target {
+            return;
+        }
+
+        Self::initialize_roots(roots);
+    }
+
     pub fn deinitialize() {
         godot_print!("deregistering rust scripting language...");
         let mut engine = Engine::singleton();
@@ -150,14 +227,62 @@ impl RustScriptExtensionLayer {
 
         godot_print!("finished deregistering rust scripting language!");
     }
+
+    /// Reloads all loaded scripts of the given class and re-creates their instances. This can be used by editor tools
+    /// to refresh a script after the library has been rebuilt.
+    pub fn reload_class(name: &str) {
+        let scripts: Vec<_> = RustScript::loaded_scripts()
+            .into_iter()
+            .filter(|script| script.bind().str_class_name() == name)
+            .collect();
+
+        if scripts.is_empty() {
+            godot_warn!(
+                "unable to reload rust script class {}, it has not been loaded!",
+                name
+            );
+            return;
+        }
+
+        for script in scripts {
+            let mut script = script.upcast::<Script>();
+            let result = script.reload_ex().keep_state(true).done();
+
+            if result != global::Error::OK {
+                godot_warn!("failed to reload rust script class {}: {:?}", name, result);
+            }
+        }
+    }
+
+    /// Deinitializes the script runtime only if the current init `level` matches the `target` level.
+    pub fn deinitialize_at(level: InitLevel, target: InitLevel) {
+        if level != target {
+            return;
+        }
+
+        Self::deinitialize();
+    }
 }
 
-fn load_rust_scripts<F: RustScriptLibInit>(lib_init_fn: F) {
-    let result = lib_init_fn();
+fn load_rust_scripts(lib_init_fns: &[&dyn RustScriptLibInit]) {
+    let scripts = lib_init_fns.iter().flat_map(|lib_init_fn| lib_init_fn());
+    let (registry, duplicates) = collect_scripts(scripts, |script| {
+        (script.class_name().to_string(), script.module_path())
+    });
+
+    for duplicate in duplicates {
+        godot_error!(
+            "godot-rust-script: script class {} is declared in {} and {}, only the script in {} is used!",
+            duplicate.class_name,
+            duplicate.first_module_path,
+            duplicate.module_path,
+            duplicate.first_module_path,
+        );
+    }
 
-    let registry: HashMap<String, RustScriptMetaData> = result
+    let registry: HashMap<String, Arc<RustScriptMetaData>> = registry
         .into_iter()
-        .map(|script| (script.class_name().to_string(), script))
+        .map(|(class_name, script)| (class_name, Arc::new(script)))
         .collect();
 
     let mut reg = SCRIPT_REGISTRY
@@ -165,4 +290,181 @@ fn load_rust_scripts<F: RustScriptLibInit>(lib_init_fn: F) {
         .expect("script registry rw lock is poisoned");
 
     *reg = registry;
+
+    LIBRARY_LOAD_ID.store(library_file_id(), Ordering::Relaxed);
+    RustScriptLanguage::clear_class_name_cache();
+}
+
+/// Hashes the path, size and modification time of the library file. The load time is used instead if the file can not
+/// be inspected, so the id still changes with every load.
+fn library_file_id() -> u64 {
+    let path = library_path();
+    let mut hasher = DefaultHasher::new();
+
+    path.hash(&mut hasher);
+
+    match std::fs::metadata(&path).and_then(|meta| Ok((meta.len(), meta.modified()?))) {
+        Ok(file_state) => file_state.hash(&mut hasher),
+        Err(err) => {
+            godot_warn!("godot-rust-script: failed to inspect the script library {path}: {err}");
+            SystemTime::now().hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+/// Absolute path of the GDExtension library that contains the scripts.
+fn library_path() -> String {
+    // SAFETY: the library pointer stays valid while the extension is loaded, and the engine initializes the string.
+    let path = unsafe {
+        GString::new_with_uninit(|ptr| {
+            sys::interface_fn!(get_library_path)(sys::get_library(), ptr);
+        })
+    };
+
+    ProjectSettings::singleton()
+        .globalize_path(&path)
+        .to_string()
+}
+
+/// Id of the currently loaded script library. Scripts compare it to the id they have been loaded with to detect if the
+/// library has been rebuilt since.
+pub(crate) fn library_load_id() -> u64 {
+    LIBRARY_LOAD_ID.load(Ordering::Relaxed)
+}
+
+/// A script class that has been declared more than once.
+#[derive(Debug, PartialEq, Eq)]
+struct DuplicateScript {
+    class_name: String,
+    /// Module of the script that has been registered.
+    first_module_path: &'static str,
+    /// Module of the script that has been discarded.
+    module_path: &'static str,
+}
+
+/// Collects scripts by their class name. Only the first script of each class is kept, all following scripts with the
+/// same class name are reported as duplicates.
+fn collect_scripts<S>(
+    scripts: impl IntoIterator<Item = S>,
+    script_id: impl Fn(&S) -> (String, &'static str),
+) -> (HashMap<String, S>, Vec<DuplicateScript>) {
+    let mut registry: HashMap<String, S> = HashMap::new();
+    let mut duplicates = Vec::new();
+
+    for script in scripts {
+        let (class_name, module_path) = script_id(&script);
+
+        match registry.entry(class_name) {
+            Entry::Occupied(entry) => duplicates.push(DuplicateScript {
+                first_module_path: script_id(entry.get()).1,
+                class_name: entry.key().to_owned(),
+                module_path,
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(script);
+            }
+        }
+    }
+
+    (registry, duplicates)
+}
+
+/// Range of argument counts a script method accepts. Arguments with a default value are optional.
+pub(crate) fn script_method_argument_count(
+    class_name: &str,
+    method: &str,
+) -> Option<RangeInclusive<usize>> {
+    let reg = SCRIPT_REGISTRY
+        .read()
+        .expect("script registry rw lock is poisoned");
+
+    reg.get(class_name)?.method(method).map(|info| {
+        let min = info.arguments.len() - info.default_arguments.len();
+
+        // variadic methods accept any number of additional arguments.
+        if info.flags & MethodFlags::VARARG.ord() != 0 {
+            return min..=usize::MAX;
+        }
+
+        min..=info.arguments.len()
+    })
+}
+
+/// Runs `f` with a default instance of the script `T`. The instance is attached to a temporary base object, which is
+/// freed again afterwards. Returns `None` if the base class can not be instantiated, e.g. because it is abstract.
+pub fn with_default_script<T: GodotScript, R>(f: impl FnOnce(&T) -> R) -> Option<R> {
+    let base = ClassDb::singleton()
+        .instantiate(&<T::Base as GodotClass>::class_name().to_string_name())
+        .try_to::<Gd<Object>>()
+        .ok()?;
+
+    let script = T::default_with_base(base.clone());
+    let result = f(&script);
+
+    drop(script);
+
+    // ref counted objects are released together with the last reference.
+    if let Err(base) = base.try_cast::<RefCounted>() {
+        base.free();
+    }
+
+    Some(result)
+}
+
+/// Label of a script instance in its string representation, `<ClassName#instance_id>`. It stands in for the whole
+/// representation while the script data is borrowed.
+pub fn script_instance_label(class_name: &str, instance_id: InstanceId) -> String {
+    format!("<{class_name}#{instance_id}>")
+}
+
+#[cfg(test)]
+mod tests {
+    use godot::obj::InstanceId;
+
+    use super::{collect_scripts, script_instance_label, DuplicateScript};
+
+    #[test]
+    fn duplicate_class_names_keep_first_script() {
+        let scripts = [
+            ("Player", "scripts::player"),
+            ("Enemy", "scripts::enemy"),
+            ("Player", "scripts::legacy::player"),
+        ];
+
+        let (registry, duplicates) = collect_scripts(scripts, |(class_name, module_path)| {
+            (class_name.to_string(), *module_path)
+        });
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry["Player"], ("Player", "scripts::player"));
+        assert_eq!(
+            duplicates,
+            vec![DuplicateScript {
+                class_name: "Player".into(),
+                first_module_path: "scripts::player",
+                module_path: "scripts::legacy::player",
+            }]
+        );
+    }
+
+    #[test]
+    fn unique_class_names_have_no_duplicates() {
+        let scripts = [("Player", "scripts::player"), ("Enemy", "scripts::enemy")];
+
+        let (registry, duplicates) = collect_scripts(scripts, |(class_name, module_path)| {
+            (class_name.to_string(), *module_path)
+        });
+
+        assert_eq!(registry.len(), 2);
+        assert!(duplicates.is_empty());
+    }
+
+    #[test]
+    fn script_instance_label_contains_class_and_id() {
+        let label = script_instance_label("Player", InstanceId::from_i64(42));
+
+        assert_eq!(label, "<Player#42>");
+    }
 }
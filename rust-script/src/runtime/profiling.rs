@@ -0,0 +1,105 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use godot::classes::native::ScriptLanguageExtensionProfilingInfo;
+use godot::meta::ClassName;
+use godot::prelude::StringName;
+use once_cell::sync::Lazy;
+
+static PROFILING_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Call statistics of every script method that has been called since profiling was started.
+static METHOD_COUNTERS: Lazy<RwLock<HashMap<(ClassName, StringName), MethodCounters>>> =
+    Lazy::new(RwLock::default);
+
+#[derive(Default)]
+struct MethodCounters {
+    call_count: AtomicU64,
+    /// Accumulated wall-clock time in microseconds.
+    total_time: AtomicU64,
+}
+
+pub(super) fn start() {
+    PROFILING_ACTIVE.store(true, Ordering::Release);
+}
+
+pub(super) fn stop() {
+    PROFILING_ACTIVE.store(false, Ordering::Release);
+
+    METHOD_COUNTERS
+        .write()
+        .expect("profiling counters are inaccessible")
+        .clear();
+}
+
+pub(super) fn is_active() -> bool {
+    PROFILING_ACTIVE.load(Ordering::Acquire)
+}
+
+pub(super) fn record_call(class_name: ClassName, method: &StringName, duration: Duration) {
+    let time = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+    let record = |counters: &MethodCounters| {
+        counters.call_count.fetch_add(1, Ordering::Relaxed);
+        counters.total_time.fetch_add(time, Ordering::Relaxed);
+    };
+
+    if let Some(counters) = METHOD_COUNTERS
+        .read()
+        .expect("profiling counters are inaccessible")
+        .get(&(class_name, method.clone()))
+    {
+        record(counters);
+        return;
+    }
+
+    record(
+        METHOD_COUNTERS
+            .write()
+            .expect("profiling counters are inaccessible")
+            .entry((class_name, method.clone()))
+            .or_default(),
+    );
+}
+
+/// Writes the accumulated call statistics into the info array provided by the engine and returns the number of written
+/// entries.
+///
+/// Nested script calls are not tracked, so the self time of a method is reported to be equal to its total time.
+///
+/// # Safety
+/// `info_array` must point to writable memory for at least `info_max` entries.
+pub(super) unsafe fn write_accumulated_data(
+    info_array: *mut ScriptLanguageExtensionProfilingInfo,
+    info_max: i32,
+) -> i32 {
+    let counters = METHOD_COUNTERS
+        .read()
+        .expect("profiling counters are inaccessible");
+
+    let info_max = usize::try_from(info_max).unwrap_or(0);
+    let mut written = 0;
+
+    for ((class_name, method), counters) in counters.iter().take(info_max) {
+        let total_time = counters.total_time.load(Ordering::Relaxed);
+        let info = ScriptLanguageExtensionProfilingInfo {
+            signature: StringName::from(format!("{class_name}::{method}")),
+            call_count: counters.call_count.load(Ordering::Relaxed),
+            total_time,
+            self_time: total_time,
+        };
+
+        // the engine hands us uninitialized memory, so the previous value must not be dropped.
+        unsafe { info_array.add(written).write(info) };
+        written += 1;
+    }
+
+    written as i32
+}
@@ -6,9 +6,7 @@
 
 use std::option::Option;
 
-use godot::classes::{
-    file_access, resource_saver::SaverFlags, FileAccess, IResourceFormatSaver, Script,
-};
+use godot::classes::{resource_saver::SaverFlags, IResourceFormatSaver, Script};
 use godot::global::{self, godot_warn};
 use godot::obj::EngineBitfield;
 use godot::prelude::{
@@ -29,7 +27,14 @@ impl IResourceFormatSaver for RustScriptResourceSaver {
             return global::Error::FAILED;
         };
 
-        let mut script: Gd<Script> = resource.cast();
+        let Ok(script) = resource.try_cast::<RustScript>() else {
+            godot_warn!(
+                "RustScriptResourceSaver: Unable to save a resource that is not a RustScript!"
+            );
+            return global::Error::ERR_INVALID_PARAMETER;
+        };
+
+        let mut script: Gd<Script> = script.upcast();
 
         godot_print!("saving rust script resource to: {}", path);
 
@@ -37,22 +42,8 @@ impl IResourceFormatSaver for RustScriptResourceSaver {
             script.set_path(&path);
         }
 
-        if !script.has_source_code() {
-            return global::Error::OK;
-        }
-
-        let handle = FileAccess::open(&path, file_access::ModeFlags::WRITE);
-
-        let mut handle = match handle {
-            Some(handle) => handle,
-            None => {
-                return global::Error::FAILED;
-            }
-        };
-
-        handle.store_string(&script.get_source_code());
-        handle.close();
-
+        // the source of a rust script is the rust module it has been declared in. The editor must never write to it, so
+        // saving the script only updates its path.
         global::Error::OK
     }
 
@@ -66,7 +57,7 @@ impl IResourceFormatSaver for RustScriptResourceSaver {
         PackedStringArray::from(&[GString::from("rs")])
     }
 
-    fn recognize_path(&self, _resource: Option<Gd<Resource>>, _path: GString) -> bool {
-        true
+    fn recognize_path(&self, _resource: Option<Gd<Resource>>, path: GString) -> bool {
+        path.to_string().ends_with(".rs")
     }
 }
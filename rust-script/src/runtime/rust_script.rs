@@ -4,7 +4,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use std::{cell::RefCell, collections::HashSet, ffi::c_void};
+use std::{cell::RefCell, collections::HashSet, ffi::c_void, sync::RwLock};
 
 use godot::classes::{
     notify::ObjectNotification, object::ConnectFlags, ClassDb, Engine, IScriptExtension, Object,
@@ -18,20 +18,23 @@ use godot::prelude::{
     godot_api, Array, Base, Callable, Dictionary, GString, Gd, GodotClass, StringName, Variant,
     VariantArray,
 };
+use once_cell::sync::Lazy;
 
 use crate::apply::Apply;
 
-use super::rust_script_instance::GodotScriptObject;
 use super::{
     downgrade_self::DowngradeSelf,
+    library_load_id,
     metadata::{Documented, ToDictionary, ToMethodDoc, ToPropertyDoc},
     rust_script_instance::{RustScriptInstance, RustScriptPlaceholder},
     rust_script_language::RustScriptLanguage,
-    SCRIPT_REGISTRY,
+    script_instance_data, SCRIPT_REGISTRY,
 };
 
 const NOTIFICATION_EXTENSION_RELOADED: i32 = 2;
 
+static LOADED_SCRIPTS: Lazy<RwLock<HashSet<InstanceId>>> = Lazy::new(RwLock::default);
+
 #[derive(GodotClass)]
 #[class(base = ScriptExtension, tool)]
 pub(crate) struct RustScript {
@@ -43,6 +46,8 @@ pub(crate) struct RustScript {
     owner_ids: Array<i64>,
 
     owners: RefCell<Vec<Gd<WeakRef>>>,
+    /// Load of the script library the script instances have been created with.
+    library_load_id: u64,
     base: Base<ScriptExtension>,
 }
 
@@ -55,9 +60,23 @@ impl RustScript {
 
         inst.bind_mut().class_name = GString::from(class_name);
 
+        LOADED_SCRIPTS
+            .write()
+            .expect("loaded scripts rw lock is poisoned")
+            .insert(inst.instance_id());
+
         inst
     }
 
+    pub fn loaded_scripts() -> Vec<Gd<Self>> {
+        LOADED_SCRIPTS
+            .read()
+            .expect("loaded scripts rw lock is poisoned")
+            .iter()
+            .filter_map(|id| Gd::try_from_instance_id(*id).ok())
+            .collect()
+    }
+
     #[func]
     pub fn get_class_name(&self) -> GString {
         self.class_name.clone()
@@ -72,14 +91,87 @@ impl RustScript {
         self.class_name.to_string()
     }
 
-    pub fn create_remote_instance(&self, base: Gd<Object>) -> Box<dyn GodotScriptObject> {
-        let reg = SCRIPT_REGISTRY.read().expect("failed to obtain read lock");
+    /// Calls a static method of the script. The engine does not route `Object.call` on a script resource to the script
+    /// language, so static methods are reached through this function.
+    #[func]
+    pub fn call_static(&self, method: StringName, args: VariantArray) -> Variant {
+        let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
 
-        let meta_data = reg
-            .get(&self.str_class_name())
-            .expect("we musst know the class name at this point");
+        let Some(class) = reg.get(&self.str_class_name()) else {
+            godot_error!("unknown rust script class {}", self.class_name);
+            return Variant::nil();
+        };
+
+        let args: Vec<Variant> = args.iter_shared().collect();
+        let arg_refs: Vec<&Variant> = args.iter().collect();
 
-        meta_data.create_data(base)
+        class
+            .call_static(method.clone(), &arg_refs)
+            .unwrap_or_else(|err| {
+                godot_error!(
+                    "failed to call static method {}::{}: error {}",
+                    self.class_name,
+                    method,
+                    err
+                );
+
+                Variant::nil()
+            })
+    }
+
+    fn property_default_value(&self, property: StringName) -> Option<Variant> {
+        let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
+
+        reg.get(&self.str_class_name())
+            .and_then(|class| class.property_default_value(property))
+    }
+
+    /// Script properties of the current instance of the object. Properties that are still at their default value are
+    /// left out, so they are initialized by the new instance instead of being overwritten.
+    fn instance_state(&self, object: &Gd<Object>) -> Vec<(StringName, Variant)> {
+        let state = match script_instance_data(object.instance_id()) {
+            Some(data) => match data.borrow() {
+                Ok(data) => data.property_state().into_iter().collect(),
+                Err(err) => {
+                    godot_warn!(
+                        "{}: unable to keep the state of {} during reload: {}",
+                        self.class_name,
+                        object.instance_id(),
+                        err
+                    );
+
+                    return Vec::new();
+                }
+            },
+
+            // placeholder instances in the editor have no script data, their properties are read from the object.
+            None => self.placeholder_state(object),
+        };
+
+        state
+            .into_iter()
+            .filter(|(name, value)| {
+                self.property_default_value(name.clone()).as_ref() != Some(value)
+            })
+            .collect()
+    }
+
+    fn placeholder_state(&self, object: &Gd<Object>) -> Vec<(StringName, Variant)> {
+        let members = self.get_members();
+
+        // the instance has been created from the previous version of the script, only its own properties can be read.
+        object
+            .get_property_list()
+            .iter_shared()
+            .filter_map(|prop| prop.get("name")?.try_to::<GString>().ok())
+            .map(|name| StringName::from(&name))
+            .filter(|name| members.contains(name))
+            .map(|name| {
+                let value = object.get(&name);
+
+                (name, value)
+            })
+            .collect()
     }
 
     #[func]
@@ -150,6 +242,7 @@ impl IScriptExtension for RustScript {
             base,
             owners: Default::default(),
             owner_ids: Default::default(),
+            library_load_id: library_load_id(),
         }
     }
 
@@ -184,7 +277,10 @@ impl IScriptExtension for RustScript {
     }
 
     fn is_tool(&self) -> bool {
-        false
+        let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
+
+        reg.get(&self.str_class_name())
+            .is_some_and(|class| class.is_tool())
     }
 
     unsafe fn instance_create(&self, mut for_object: Gd<Object>) -> *mut c_void {
@@ -192,8 +288,12 @@ impl IScriptExtension for RustScript {
             .borrow_mut()
             .push(godot::global::weakref(&for_object.to_variant()).to());
 
-        let data = self.create_remote_instance(for_object.clone());
-        let instance = RustScriptInstance::new(data, for_object.clone(), self.to_gd());
+        let meta_data = RustScriptLanguage::script_meta_data(&self.str_class_name())
+            .expect("we musst know the class name at this point");
+
+        let data = meta_data.create_data(for_object.clone());
+        let instance =
+            RustScriptInstance::new(data, meta_data, self.to_gd(), for_object.instance_id());
 
         let callbale_args = VariantArray::from(&[for_object.to_variant()]);
 
@@ -223,14 +323,13 @@ impl IScriptExtension for RustScript {
         true
     }
 
-    fn has_property_default_value(&self, _property: StringName) -> bool {
-        // default values are currently not exposed
-        false
+    fn has_property_default_value(&self, property: StringName) -> bool {
+        self.property_default_value(property).is_some()
     }
 
-    fn get_property_default_value(&self, #[expect(unused)] property: StringName) -> Variant {
-        // default values are currently not exposed
-        Variant::nil()
+    fn get_property_default_value(&self, property: StringName) -> Variant {
+        self.property_default_value(property)
+            .unwrap_or_else(Variant::nil)
     }
 
     fn get_script_signal_list(&self) -> Array<Dictionary> {
@@ -297,37 +396,51 @@ impl IScriptExtension for RustScript {
     fn has_method(&self, method_name: StringName) -> bool {
         let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
 
-        reg.get(&self.str_class_name()).is_some_and(|class| {
-            class
-                .methods()
-                .iter()
-                .any(|method| method.method_name == method_name.to_string())
-        })
+        reg.get(&self.str_class_name())
+            .is_some_and(|class| class.method_index(&method_name.to_string()).is_some())
     }
 
     fn get_constants(&self) -> Dictionary {
-        Dictionary::new()
-    }
-    fn get_method_info(&self, method_name: StringName) -> Dictionary {
         let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
 
         reg.get(&self.str_class_name())
-            .and_then(|class| {
+            .map(|class| {
                 class
-                    .methods()
+                    .constants()
                     .iter()
-                    .find(|method| method.method_name == method_name.to_string())
-                    .map(|method| MethodInfo::from(method).to_dict())
+                    .map(|constant| (constant.name, (constant.value)()))
+                    .collect()
             })
             .unwrap_or_default()
     }
+    fn get_method_info(&self, method_name: StringName) -> Dictionary {
+        let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
+
+        reg.get(&self.str_class_name())
+            .and_then(|class| class.method(&method_name.to_string()))
+            .map(|method| MethodInfo::from(method).to_dict())
+            .unwrap_or_default()
+    }
 
     fn get_documentation(&self) -> Array<Dictionary> {
-        let (methods, props, signals, description): (
+        let (
+            methods,
+            props,
+            signals,
+            brief_description,
+            description,
+            tutorials,
+            is_deprecated,
+            is_experimental,
+        ): (
             Array<Dictionary>,
             Array<Dictionary>,
             Array<Dictionary>,
             &'static str,
+            &'static str,
+            Array<Dictionary>,
+            bool,
+            bool,
         ) = {
             let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
 
@@ -344,6 +457,7 @@ impl IScriptExtension for RustScript {
                     let props = class
                         .properties()
                         .iter()
+                        .filter(|prop| !prop.is_category())
                         .map(|prop| {
                             Documented::<PropertyInfo>::from(prop.to_owned()).to_property_doc()
                         })
@@ -357,9 +471,27 @@ impl IScriptExtension for RustScript {
                         })
                         .collect();
 
-                    let description = class.description();
+                    let tutorials = class
+                        .tutorials()
+                        .iter()
+                        .map(|(title, link)| {
+                            Dictionary::new().apply(|dict| {
+                                dict.set("title", *title);
+                                dict.set("link", *link);
+                            })
+                        })
+                        .collect();
 
-                    (methods, props, signals, description)
+                    (
+                        methods,
+                        props,
+                        signals,
+                        class.brief_description(),
+                        class.description(),
+                        tutorials,
+                        class.is_deprecated(),
+                        class.is_experimental(),
+                    )
                 })
                 .unwrap_or_default()
         };
@@ -367,9 +499,9 @@ impl IScriptExtension for RustScript {
         let class_doc = Dictionary::new().apply(|dict| {
             dict.set(GString::from("name"), self.get_class_name());
             dict.set(GString::from("inherits"), self.get_instance_base_type());
-            dict.set(GString::from("brief_description"), GString::new());
+            dict.set(GString::from("brief_description"), brief_description);
             dict.set(GString::from("description"), description);
-            dict.set(GString::from("tutorials"), VariantArray::new());
+            dict.set(GString::from("tutorials"), tutorials);
             dict.set(GString::from("constructors"), VariantArray::new());
             dict.set(GString::from("methods"), methods);
             dict.set(GString::from("operators"), VariantArray::new());
@@ -379,8 +511,8 @@ impl IScriptExtension for RustScript {
             dict.set(GString::from("properties"), props);
             dict.set(GString::from("theme_properties"), VariantArray::new());
             dict.set(GString::from("annotations"), VariantArray::new());
-            dict.set(GString::from("is_deprecated"), false);
-            dict.set(GString::from("is_experimental"), false);
+            dict.set(GString::from("is_deprecated"), is_deprecated);
+            dict.set(GString::from("is_experimental"), is_experimental);
             dict.set(GString::from("is_script_doc"), true);
             dict.set(GString::from("script_path"), self.base().get_path());
         });
@@ -389,11 +521,14 @@ impl IScriptExtension for RustScript {
     }
 
     fn editor_can_reload_from_file(&mut self) -> bool {
-        true
+        // saving a rust file does not change the script until the library has been rebuilt and reloaded.
+        self.library_load_id != library_load_id()
     }
 
     // godot script reload hook
-    fn reload(&mut self, _keep_state: bool) -> godot::global::Error {
+    fn reload(&mut self, keep_state: bool) -> godot::global::Error {
+        self.library_load_id = library_load_id();
+
         let owners = self.owners.borrow().clone();
 
         owners.iter().for_each(|owner| {
@@ -405,26 +540,47 @@ impl IScriptExtension for RustScript {
                 }
             };
 
+            let state = if keep_state {
+                self.instance_state(&object)
+            } else {
+                Vec::new()
+            };
+
             // clear script to destroy script instance.
             object.set_script(&Variant::nil());
 
             self.downgrade_gd(|self_gd| {
                 // re-assign script to create new instance.
                 object.set_script(&self_gd.to_variant());
-            })
+            });
+
+            state
+                .iter()
+                .for_each(|(name, value)| object.set(name, value));
         });
 
         godot::global::Error::OK
     }
 
     fn on_notification(&mut self, what: ObjectNotification) {
-        if let ObjectNotification::Unknown(NOTIFICATION_EXTENSION_RELOADED) = what {
-            godot_print!(
-                "RustScript({}): received extension reloaded notification!",
-                self.str_class_name()
-            );
-
-            self.reload(false);
+        match what {
+            ObjectNotification::Unknown(NOTIFICATION_EXTENSION_RELOADED) => {
+                godot_print!(
+                    "RustScript({}): received extension reloaded notification!",
+                    self.str_class_name()
+                );
+
+                self.reload(true);
+            }
+
+            ObjectNotification::PREDELETE => {
+                LOADED_SCRIPTS
+                    .write()
+                    .expect("loaded scripts rw lock is poisoned")
+                    .remove(&self.base().instance_id());
+            }
+
+            _ => (),
         }
     }
 
@@ -432,23 +588,29 @@ impl IScriptExtension for RustScript {
         false
     }
 
-    fn inherits_script(&self, #[expect(unused)] script: Gd<Script>) -> bool {
-        false
+    /// Rust scripts can not inherit from other scripts, so a script only inherits from its own class.
+    fn inherits_script(&self, script: Gd<Script>) -> bool {
+        script
+            .try_cast::<RustScript>()
+            .is_ok_and(|script| script.bind().str_class_name() == self.str_class_name())
     }
 
     fn instance_has(&self, object: Gd<Object>) -> bool {
-        #[expect(unused)]
         let Some(script): Option<Gd<RustScript>> = object.get_script().try_to().ok() else {
             return false;
         };
 
-        true
+        // the same script class can be loaded as separate resources, so the class names are compared instead of the
+        // script resources.
+        script.bind().str_class_name() == self.str_class_name()
     }
 
     #[cfg(since_api = "4.2")]
-    fn has_static_method(&self, #[expect(unused)] method: StringName) -> bool {
-        // static methods are currently not supported
-        false
+    fn has_static_method(&self, method: StringName) -> bool {
+        let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
+
+        reg.get(&self.str_class_name())
+            .is_some_and(|class| class.has_static_method(&method.to_string()))
     }
 
     fn get_member_line(&self, #[expect(unused)] member: StringName) -> i32 {
@@ -463,6 +625,7 @@ impl IScriptExtension for RustScript {
                 class
                     .properties()
                     .iter()
+                    .filter(|prop| !prop.is_category())
                     .map(|prop| StringName::from(prop.property_name))
                     .collect()
             })
@@ -474,8 +637,12 @@ impl IScriptExtension for RustScript {
     }
 
     fn get_rpc_config(&self) -> Variant {
-        godot_warn!("godot-rust-script: rpc config is unsupported!");
-        Variant::nil()
+        let reg = SCRIPT_REGISTRY.read().expect("unable to obtain read lock");
+
+        reg.get(&self.str_class_name())
+            .map(|class| class.rpc_config())
+            .unwrap_or_default()
+            .to_variant()
     }
 
     #[cfg(since_api = "4.4")]
@@ -5,19 +5,42 @@
  */
 
 use std::any::Any;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 use std::{collections::HashMap, ops::DerefMut};
 
-use godot::classes::Script;
+use godot::classes::{Engine, Script};
 use godot::meta::{MethodInfo, PropertyInfo};
 use godot::obj::script::{ScriptInstance, SiMut};
-use godot::prelude::{GString, Gd, Object, StringName, Variant, VariantType};
+use godot::obj::InstanceId;
+use godot::prelude::{
+    GString, Gd, Object, PackedStringArray, StringName, ToGodot, Variant, VariantType,
+};
 use godot_cell::blocking::GdCell;
+use once_cell::sync::Lazy;
 
 use super::call_context::GenericContext;
+use super::profiling;
 use super::Context;
 use super::{rust_script::RustScript, rust_script_language::RustScriptLanguage, SCRIPT_REGISTRY};
+use crate::static_script_registry::RustScriptMetaData;
 use crate::GodotScript;
 
+/// Number of arguments of a script method, like `Callable::get_argument_count`. Arguments with a default value are
+/// counted, the additional arguments of a variadic method are not.
+#[cfg(since_api = "4.3")]
+fn method_argument_count(class_name: &str, method: &StringName) -> Option<u32> {
+    let count = super::script_method_argument_count(class_name, &method.to_string())?;
+
+    let count = if *count.end() == usize::MAX {
+        *count.start()
+    } else {
+        *count.end()
+    };
+
+    Some(count as u32)
+}
+
 fn script_method_list(script: &Gd<RustScript>) -> Box<[MethodInfo]> {
     let rs = script.bind();
     let class_name = rs.str_class_name();
@@ -50,6 +73,51 @@ fn script_property_list(script: &Gd<RustScript>) -> Box<[PropertyInfo]> {
     props
 }
 
+/// Data cell of a script instance. The cell is shared with [`ScriptRef`](crate::ScriptRef) and
+/// [`ScriptMut`](crate::ScriptMut) guards, so it stays alive while a guard borrows it, even if the object or its script
+/// instance is freed in the meantime.
+pub(crate) type InstanceDataCell = Arc<GdCell<Box<dyn GodotScriptObject>>>;
+
+/// Data cells of all script instances by the id of their object, so rust code can access the script of an object
+/// without going through the engine.
+static INSTANCE_DATA: Lazy<RwLock<HashMap<InstanceId, SharedInstanceData>>> =
+    Lazy::new(RwLock::default);
+
+struct SharedInstanceData(InstanceDataCell);
+
+// script data is not required to be Send or Sync. The blocking cell synchronizes borrows across threads. The last clone
+// of the cell can be dropped on another thread, the same way the engine can free an object on any thread when threads
+// are enabled.
+unsafe impl Send for SharedInstanceData {}
+unsafe impl Sync for SharedInstanceData {}
+
+/// Data cell of the script instance that is currently attached to the object with the given id.
+pub(crate) fn script_instance_data(instance_id: InstanceId) -> Option<InstanceDataCell> {
+    INSTANCE_DATA
+        .read()
+        .expect("instance data rw lock is poisoned")
+        .get(&instance_id)
+        .map(|data| Arc::clone(&data.0))
+}
+
+const CONFIGURATION_WARNINGS_METHOD: &str = "_get_configuration_warnings";
+
+fn configuration_warnings(data: &dyn GodotScriptObject) -> PackedStringArray {
+    let mut warnings: PackedStringArray = data
+        .missing_required_exports()
+        .into_iter()
+        .map(|name| {
+            GString::from(format!(
+                "Required property \"{name}\" has not been assigned."
+            ))
+        })
+        .collect();
+
+    warnings.extend_array(&data.configuration_warnings());
+
+    warnings
+}
+
 pub trait GodotScriptObject {
     fn set(&mut self, name: StringName, value: Variant) -> bool;
     fn get(&self, name: StringName) -> Option<Variant>;
@@ -61,7 +129,14 @@ pub trait GodotScriptObject {
     ) -> Result<Variant, godot::sys::GDExtensionCallErrorType>;
     fn to_string(&self) -> String;
     fn property_state(&self) -> HashMap<StringName, Variant>;
-
+    fn on_validate(&mut self);
+    fn dynamic_properties(&self) -> Option<Vec<PropertyInfo>>;
+    fn get_fallback(&self, name: StringName) -> Option<Variant>;
+    fn set_fallback(&mut self, name: StringName, value: Variant) -> bool;
+    fn missing_required_exports(&self) -> Vec<StringName>;
+    fn configuration_warnings(&self) -> PackedStringArray;
+
+    fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
@@ -91,16 +166,49 @@ impl<T: GodotScript + 'static> GodotScriptObject for T {
         GodotScript::property_state(self)
     }
 
+    fn on_validate(&mut self) {
+        GodotScript::on_validate(self)
+    }
+
+    fn dynamic_properties(&self) -> Option<Vec<PropertyInfo>> {
+        GodotScript::dynamic_properties(self)
+    }
+
+    fn get_fallback(&self, name: StringName) -> Option<Variant> {
+        GodotScript::get_fallback(self, name)
+    }
+
+    fn set_fallback(&mut self, name: StringName, value: Variant) -> bool {
+        GodotScript::set_fallback(self, name, value)
+    }
+
+    fn missing_required_exports(&self) -> Vec<StringName> {
+        GodotScript::missing_required_exports(self)
+    }
+
+    fn configuration_warnings(&self) -> PackedStringArray {
+        GodotScript::configuration_warnings(self)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
 }
 
 pub(crate) struct RustScriptInstance {
-    data: GdCell<Box<dyn GodotScriptObject>>,
+    /// Shared with the instance data registry and script guards, the cell keeps its address when the instance is moved
+    /// into the engine.
+    data: InstanceDataCell,
+    instance_id: InstanceId,
 
-    script: Gd<RustScript>,
     generic_script: Gd<Script>,
+    /// Metadata of the script class at the time the instance was created. Instances are recreated when the scripts
+    /// are reloaded, so lookups never have to go through the script registry.
+    meta_data: Arc<RustScriptMetaData>,
     property_list: Box<[PropertyInfo]>,
     method_list: Box<[MethodInfo]>,
 }
@@ -108,15 +216,47 @@ pub(crate) struct RustScriptInstance {
 impl RustScriptInstance {
     pub fn new(
         data: Box<dyn GodotScriptObject>,
-        _gd_object: Gd<Object>,
+        meta_data: Arc<RustScriptMetaData>,
         script: Gd<RustScript>,
+        instance_id: InstanceId,
     ) -> Self {
+        // shared with guards that can outlive the instance, see `SharedInstanceData` for thread safety.
+        #[allow(clippy::arc_with_non_send_sync)]
+        let data = Arc::new(GdCell::new(data));
+
+        INSTANCE_DATA
+            .write()
+            .expect("instance data rw lock is poisoned")
+            .insert(instance_id, SharedInstanceData(Arc::clone(&data)));
+
         Self {
-            data: GdCell::new(data),
-            generic_script: script.clone().upcast(),
-            property_list: script_property_list(&script),
-            method_list: script_method_list(&script),
-            script,
+            data,
+            instance_id,
+            generic_script: script.upcast(),
+            property_list: meta_data
+                .properties()
+                .iter()
+                .map(PropertyInfo::from)
+                .collect(),
+            method_list: meta_data.methods().iter().map(MethodInfo::from).collect(),
+            meta_data,
+        }
+    }
+}
+
+impl Drop for RustScriptInstance {
+    fn drop(&mut self) {
+        let mut instances = INSTANCE_DATA
+            .write()
+            .expect("instance data rw lock is poisoned");
+
+        // a reloaded script can create the new instance of the object before the old one is dropped.
+        let is_current = instances
+            .get(&self.instance_id)
+            .is_some_and(|data| Arc::ptr_eq(&data.0, &self.data));
+
+        if is_current {
+            instances.remove(&self.instance_id);
         }
     }
 }
@@ -125,14 +265,20 @@ impl ScriptInstance for RustScriptInstance {
     type Base = Object;
 
     fn class_name(&self) -> GString {
-        script_class_name(&self.script)
+        GString::from(self.meta_data.class_name().to_string())
     }
 
     fn set_property(this: SiMut<Self>, name: StringName, value: &Variant) -> bool {
         let cell_ref = &this.data;
         let mut mut_data = cell_ref.borrow_mut().unwrap();
 
-        mut_data.set(name, value.to_owned())
+        let is_set = mut_data.set(name, value.to_owned());
+
+        if is_set && Engine::singleton().is_editor_hint() {
+            mut_data.on_validate();
+        }
+
+        is_set
     }
 
     fn get_property(&self, name: StringName) -> Option<Variant> {
@@ -142,7 +288,19 @@ impl ScriptInstance for RustScriptInstance {
     }
 
     fn get_property_list(&self) -> Vec<PropertyInfo> {
-        self.property_list.to_vec()
+        // dynamic properties can not be queried while a script method holds the data mutably.
+        let dynamic_properties = self
+            .data
+            .borrow()
+            .ok()
+            .and_then(|data| data.dynamic_properties())
+            .unwrap_or_default();
+
+        self.property_list
+            .iter()
+            .cloned()
+            .chain(dynamic_properties)
+            .collect()
     }
 
     fn get_method_list(&self) -> Vec<MethodInfo> {
@@ -154,7 +312,8 @@ impl ScriptInstance for RustScriptInstance {
         method: StringName,
         args: &[&Variant],
     ) -> Result<Variant, godot::sys::GDExtensionCallErrorType> {
-        let cell: *const _ = &this.data;
+        let cell: *const _ = &*this.data;
+        let class_name = this.meta_data.class_name();
 
         let base = this.base_mut();
 
@@ -164,7 +323,22 @@ impl ScriptInstance for RustScriptInstance {
 
         let context = unsafe { GenericContext::new(cell, data_ptr, base) };
 
-        data.call(method, args, context)
+        let call_start = profiling::is_active().then(Instant::now);
+        let result = data.call(method.clone(), args, context);
+
+        if let Some(call_start) = call_start {
+            profiling::record_call(class_name, &method, call_start.elapsed());
+        }
+
+        // the editor asks every node for its configuration warnings. Scripts that do not implement the method
+        // themselves report their unassigned required exports and the warnings of the script.
+        if result == Err(godot::sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD)
+            && method == StringName::from(CONFIGURATION_WARNINGS_METHOD)
+        {
+            return Ok(configuration_warnings(&**data).to_variant());
+        }
+
+        result
     }
 
     fn get_script(&self) -> &Gd<Script> {
@@ -176,9 +350,9 @@ impl ScriptInstance for RustScriptInstance {
     }
 
     fn has_method(&self, method_name: StringName) -> bool {
-        self.method_list
-            .iter()
-            .any(|method| method.method_name == method_name)
+        self.meta_data
+            .method_index(&method_name.to_string())
+            .is_some()
     }
 
     fn get_property_type(&self, name: StringName) -> godot::sys::VariantType {
@@ -190,19 +364,30 @@ impl ScriptInstance for RustScriptInstance {
     }
 
     fn to_string(&self) -> GString {
-        // self.data.to_string().into()
-        GString::new()
+        // the engine can request the string representation while a script method is running, e.g. when the base object
+        // is printed from inside the script.
+        match self.data.borrow() {
+            Ok(data) => data.to_string().into(),
+            Err(_) => super::script_instance_label(
+                &self.meta_data.class_name().to_string(),
+                self.instance_id,
+            )
+            .into(),
+        }
     }
 
     fn get_property_state(&self) -> Vec<(StringName, Variant)> {
-        self.get_property_list()
+        let mut state = self.data.borrow().unwrap().property_state();
+
+        // keep the order of the property list, state entries that are not part of the list are appended at the end.
+        let mut ordered_state: Vec<_> = self
+            .property_list
             .iter()
-            .map(|prop| &prop.property_name)
-            .filter_map(|name| {
-                self.get_property(name.to_owned())
-                    .map(|value| (name.to_owned(), value))
-            })
-            .collect()
+            .filter_map(|prop| state.remove_entry(&prop.property_name))
+            .collect();
+
+        ordered_state.extend(state);
+        ordered_state
     }
 
     fn get_language(&self) -> Gd<godot::classes::ScriptLanguage> {
@@ -217,20 +402,22 @@ impl ScriptInstance for RustScriptInstance {
 
     fn on_refcount_incremented(&self) {}
 
-    fn property_get_fallback(&self, _name: StringName) -> Option<Variant> {
-        None
+    fn property_get_fallback(&self, name: StringName) -> Option<Variant> {
+        let guard = self.data.borrow().unwrap();
+
+        guard.get_fallback(name)
     }
 
-    fn property_set_fallback(_this: SiMut<Self>, _name: StringName, _value: &Variant) -> bool {
-        false
+    fn property_set_fallback(this: SiMut<Self>, name: StringName, value: &Variant) -> bool {
+        let cell_ref = &this.data;
+        let mut mut_data = cell_ref.borrow_mut().unwrap();
+
+        mut_data.set_fallback(name, value.to_owned())
     }
 
     #[cfg(since_api = "4.3")]
     fn get_method_argument_count(&self, method: StringName) -> Option<u32> {
-        self.method_list
-            .iter()
-            .find(|m| m.method_name == method)
-            .map(|method| method.arguments.len() as u32)
+        method_argument_count(&self.meta_data.class_name().to_string(), &method)
     }
 }
 
@@ -350,9 +537,6 @@ impl ScriptInstance for RustScriptPlaceholder {
 
     #[cfg(since_api = "4.3")]
     fn get_method_argument_count(&self, method: StringName) -> Option<u32> {
-        self.method_list
-            .iter()
-            .find(|m| m.method_name == method)
-            .map(|method| method.arguments.len() as u32)
+        method_argument_count(&self.script.bind().str_class_name(), &method)
     }
 }
@@ -4,56 +4,100 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::collections::HashMap;
 use std::ffi::{c_void, OsStr};
+use std::sync::{Arc, RwLock};
 
 use godot::classes::native::ScriptLanguageExtensionProfilingInfo;
 #[cfg(since_api = "4.3")]
 use godot::classes::script_language::ScriptNameCasing;
-use godot::classes::{Engine, FileAccess, IScriptLanguageExtension, ProjectSettings, Script};
+use godot::classes::script_language_extension::{CodeCompletionKind, LookupResultType};
+use godot::classes::{
+    EditorInterface, EditorSettings, Engine, FileAccess, IScriptLanguageExtension, Os,
+    ProjectSettings, Script,
+};
 use godot::global;
+use godot::meta::ToGodot;
 use godot::obj::Base;
 use godot::prelude::{
     godot_api, Array, Dictionary, GString, Gd, GodotClass, Object, PackedStringArray, StringName,
     Variant, VariantArray,
 };
 use itertools::Itertools;
+use once_cell::sync::Lazy;
 
 use crate::apply::Apply;
 use crate::editor_ui_hacks::{show_editor_toast, EditorToasterSeverity};
 use crate::static_script_registry::RustScriptMetaData;
 
-use super::{rust_script::RustScript, SCRIPT_REGISTRY};
+use super::{profiling, rust_script::RustScript, SCRIPT_REGISTRY};
+
+/// Class names of script paths. The editor resolves the class of every script file on each scan of the project.
+static CLASS_NAMES_BY_PATH: Lazy<RwLock<HashMap<String, String>>> = Lazy::new(RwLock::default);
 
 #[derive(GodotClass)]
 #[class(base = ScriptLanguageExtension, tool)]
 pub(super) struct RustScriptLanguage {
-    scripts_src_dir: Option<&'static str>,
+    scripts_src_dirs: Vec<&'static str>,
 }
 
 #[godot_api]
 impl RustScriptLanguage {
-    pub fn new(scripts_src_dir: Option<&'static str>) -> Gd<Self> {
-        Gd::from_object(Self { scripts_src_dir })
+    pub fn new(scripts_src_dirs: Vec<&'static str>) -> Gd<Self> {
+        Gd::from_object(Self { scripts_src_dirs })
     }
 
     pub fn path_to_class_name(path: &GString) -> String {
-        std::path::Path::new(&path.to_string())
+        let path = path.to_string();
+
+        if let Some(class_name) = CLASS_NAMES_BY_PATH
+            .read()
+            .expect("class name cache rw lock is poisoned")
+            .get(&path)
+        {
+            return class_name.clone();
+        }
+
+        let file_stem = Self::path_to_file_stem(&path);
+
+        let class_name = {
+            let reg = SCRIPT_REGISTRY
+                .read()
+                .expect("unable to obtain read access");
+
+            resolve_class_name(
+                &file_stem,
+                reg.iter().map(|(class_name, meta)| {
+                    (class_name.as_str(), meta.class_prefix(), meta.type_name())
+                }),
+            )
+        };
+
+        CLASS_NAMES_BY_PATH
+            .write()
+            .expect("class name cache rw lock is poisoned")
+            .insert(path, class_name.clone());
+
+        class_name
+    }
+
+    /// Forgets all resolved class names, they have to be resolved again after the scripts have been reloaded.
+    pub fn clear_class_name_cache() {
+        CLASS_NAMES_BY_PATH
+            .write()
+            .expect("class name cache rw lock is poisoned")
+            .clear();
+    }
+
+    fn path_to_file_stem(path: &str) -> String {
+        std::path::Path::new(path)
             .file_name()
             .and_then(OsStr::to_str)
             .unwrap()
             .rsplit_once('.')
             .unwrap()
             .0
-            .split('_')
-            .map(|part| {
-                let mut chars = part.chars();
-                let first = chars.next().unwrap();
-
-                let part: String = first.to_uppercase().chain(chars).collect();
-
-                part
-            })
-            .join("")
+            .to_owned()
     }
 
     pub fn singleton() -> Option<Gd<Self>> {
@@ -62,12 +106,32 @@ impl RustScriptLanguage {
             .map(|gd| gd.cast())
     }
 
-    pub fn script_meta_data(class_name: &str) -> Option<RustScriptMetaData> {
+    pub fn script_meta_data(class_name: &str) -> Option<Arc<RustScriptMetaData>> {
         let reg = SCRIPT_REGISTRY
             .read()
             .expect("unable to obtain read access");
 
-        reg.get(class_name).map(ToOwned::to_owned)
+        reg.get(class_name).cloned()
+    }
+
+    /// Metadata of the script that is open in the script editor. Falls back to the script attached to the owner if
+    /// the script path is not known.
+    fn edited_script_meta_data(
+        path: &GString,
+        owner: Option<Gd<Object>>,
+    ) -> Option<Arc<RustScriptMetaData>> {
+        let class_name = if path.is_empty() {
+            owner?
+                .get_script()
+                .try_to::<Gd<RustScript>>()
+                .ok()?
+                .bind()
+                .str_class_name()
+        } else {
+            Self::path_to_class_name(path)
+        };
+
+        Self::script_meta_data(&class_name)
     }
 }
 
@@ -136,20 +200,23 @@ impl IScriptLanguageExtension for RustScriptLanguage {
 
     fn init(_base: Base<Self::Base>) -> Self {
         Self {
-            scripts_src_dir: None,
+            scripts_src_dirs: Vec::new(),
         }
     }
 
     /// validate that the path of a new rust script is valid. Constraints for script locations can be enforced here.
     fn validate_path(&self, path: GString) -> GString {
-        let Some(rs_root) = self
-            .scripts_src_dir
-            .map(|path| ProjectSettings::singleton().localize_path(path))
-        else {
+        if self.scripts_src_dirs.is_empty() {
             return GString::from("Unable to validate script location! RustScript source location is known in the current execution context.");
-        };
+        }
+
+        let is_in_script_root = self.scripts_src_dirs.iter().any(|rs_root| {
+            let rs_root = ProjectSettings::singleton().localize_path(*rs_root);
 
-        if !path.to_string().starts_with(&rs_root.to_string()) {
+            path.to_string().starts_with(&rs_root.to_string())
+        });
+
+        if !is_in_script_root {
             return GString::from("rust file is not part of the scripts crate!");
         }
 
@@ -191,6 +258,10 @@ impl IScriptLanguageExtension for RustScriptLanguage {
         Dictionary::new().apply(|dict| {
             dict.set("name", class_name);
             dict.set("base_type", script.base_type_name());
+
+            if let Some(icon_path) = script.icon_path() {
+                dict.set("icon_path", icon_path);
+            }
         })
     }
 
@@ -200,10 +271,14 @@ impl IScriptLanguageExtension for RustScriptLanguage {
 
     fn open_in_external_editor(
         &mut self,
-        _script: Option<Gd<Script>>,
-        _line: i32,
-        _col: i32,
+        script: Option<Gd<Script>>,
+        line: i32,
+        col: i32,
     ) -> global::Error {
+        if let Some(result) = script.and_then(|script| launch_external_editor(&script, line, col)) {
+            return result;
+        }
+
         show_editor_toast(
             "Editing rust scripts from inside Godot is currently not supported.",
             EditorToasterSeverity::Warning,
@@ -223,7 +298,7 @@ impl IScriptLanguageExtension for RustScriptLanguage {
     fn validate(
         &self,
         _script: GString,
-        _path: GString,
+        path: GString,
         _validate_functions: bool,
         _validate_errors: bool,
         _validate_warnings: bool,
@@ -231,8 +306,26 @@ impl IScriptLanguageExtension for RustScriptLanguage {
     ) -> Dictionary {
         let mut validation = Dictionary::new();
 
-        validation.set("valid", "true");
-        validation.set("errors", VariantArray::new());
+        // the script source can not be compiled by the editor. We can only check if the class is part of the currently
+        // loaded library.
+        let is_compiled =
+            path.is_empty() || Self::script_meta_data(&Self::path_to_class_name(&path)).is_some();
+
+        let errors: VariantArray = (!is_compiled)
+            .then(|| {
+                Dictionary::new()
+                    .apply(|error| {
+                        error.set("line", 0);
+                        error.set("column", 0);
+                        error.set("message", "Class not found in compiled library");
+                    })
+                    .to_variant()
+            })
+            .into_iter()
+            .collect();
+
+        validation.set("valid", errors.is_empty());
+        validation.set("errors", errors);
         validation.set("functions", VariantArray::new());
         validation.set("warnings", VariantArray::new());
 
@@ -240,7 +333,9 @@ impl IScriptLanguageExtension for RustScriptLanguage {
     }
 
     // godot hook to trigger script reload
-    fn reload_all_scripts(&mut self) {}
+    fn reload_all_scripts(&mut self) {
+        Self::clear_class_name_cache();
+    }
 
     fn init_ext(&mut self) {}
 
@@ -281,9 +376,19 @@ impl IScriptLanguageExtension for RustScriptLanguage {
         ScriptNameCasing::SNAKE_CASE
     }
 
-    #[expect(unused_variables)]
     fn complete_code(&self, code: GString, path: GString, owner: Option<Gd<Object>>) -> Dictionary {
-        Dictionary::new()
+        let prefix = completion_prefix(&code.to_string());
+
+        let options: Array<Dictionary> = Self::edited_script_meta_data(&path, owner)
+            .map(|class| completion_options(&class, &prefix))
+            .unwrap_or_default();
+
+        Dictionary::new().apply(|dict| {
+            dict.set("result", global::Error::OK);
+            dict.set("force", false);
+            dict.set("call_hint", GString::new());
+            dict.set("options", options);
+        })
     }
 
     #[expect(unused_variables)]
@@ -294,7 +399,31 @@ impl IScriptLanguageExtension for RustScriptLanguage {
         path: GString,
         owner: Option<Gd<Object>>,
     ) -> Dictionary {
-        Dictionary::new()
+        let symbol = symbol.to_string();
+        let lookup = Self::edited_script_meta_data(&path, owner)
+            .and_then(|class| lookup_member(&class, &symbol))
+            .or_else(|| {
+                Self::script_meta_data(&symbol).map(|class| {
+                    (
+                        LookupResultType::CLASS,
+                        class.class_name().to_string(),
+                        None,
+                    )
+                })
+            });
+
+        Dictionary::new().apply(|dict| {
+            let Some((result_type, class_name, class_member)) = lookup else {
+                dict.set("result", global::Error::ERR_CANT_RESOLVE);
+                dict.set("type", LookupResultType::SCRIPT_LOCATION);
+                return;
+            };
+
+            dict.set("result", global::Error::OK);
+            dict.set("type", result_type);
+            dict.set("class_name", class_name);
+            dict.set("class_member", class_member.unwrap_or_default());
+        })
     }
 
     fn auto_indent_code(
@@ -384,22 +513,32 @@ impl IScriptLanguageExtension for RustScriptLanguage {
         Array::default()
     }
 
-    #[expect(unused_variables)]
-    fn reload_tool_script(&mut self, script: Option<Gd<Script>>, soft_reload: bool) {}
-    fn profiling_start(&mut self) {}
-    fn profiling_stop(&mut self) {}
+    fn reload_tool_script(&mut self, script: Option<Gd<Script>>, soft_reload: bool) {
+        let Some(script) = script else {
+            return;
+        };
+
+        reload_script(script, !soft_reload);
+    }
+    fn profiling_start(&mut self) {
+        profiling::start();
+    }
+
+    fn profiling_stop(&mut self) {
+        profiling::stop();
+    }
 
     #[cfg(since_api = "4.3")]
     #[expect(unused_variables)]
     fn profiling_set_save_native_calls(&mut self, enable: bool) {}
 
-    #[expect(unused_variables)]
     unsafe fn profiling_get_accumulated_data(
         &mut self,
         info_array: *mut ScriptLanguageExtensionProfilingInfo,
         info_max: i32,
     ) -> i32 {
-        0
+        // SAFETY: the engine provides an array with space for `info_max` entries.
+        unsafe { profiling::write_accumulated_data(info_array, info_max) }
     }
 
     #[expect(unused_variables)]
@@ -412,10 +551,328 @@ impl IScriptLanguageExtension for RustScriptLanguage {
     }
 
     #[cfg(since_api = "4.4")]
-    #[expect(unused_variables)]
     fn reload_scripts(&mut self, scripts: Array<Variant>, soft: bool) {
-        use godot::global::godot_warn;
+        scripts
+            .iter_shared()
+            .filter_map(|script| script.try_to::<Gd<Script>>().ok())
+            .for_each(|script| reload_script(script, !soft));
+    }
+}
+
+/// Finds the registered class that belongs to a script file. A class that is named exactly like the file is
+/// preferred, otherwise the `snake_case` file name is converted to `CamelCase`. Scripts can be registered with a
+/// class prefix which is not part of the file name. Renamed classes are still found by the name of their rust type.
+fn resolve_class_name<'a>(
+    file_stem: &str,
+    classes: impl Iterator<Item = (&'a str, &'a str, &'a str)> + Clone,
+) -> String {
+    let script_name = snake_to_camel_case(file_stem);
+
+    let class_name = [file_stem, script_name.as_str()]
+        .into_iter()
+        .find_map(|candidate| {
+            classes
+                .clone()
+                .find(|(class_name, prefix, type_name)| {
+                    *class_name == candidate
+                        || class_name.strip_prefix(prefix) == Some(candidate)
+                        || *type_name == candidate
+                })
+                .map(|(class_name, ..)| class_name.to_owned())
+        });
+
+    class_name.unwrap_or(script_name)
+}
 
-        godot_warn!("Reloading Rust Scripts is currently a no-op!");
+fn snake_to_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap();
+
+            let part: String = first.to_uppercase().chain(chars).collect();
+
+            part
+        })
+        .join("")
+}
+
+/// Godot marks the position of the cursor in the code that should be completed with this character.
+const COMPLETION_CURSOR: char = '\u{FFFF}';
+
+/// The partial identifier in front of the cursor.
+fn completion_prefix(code: &str) -> String {
+    let before_cursor = code
+        .split_once(COMPLETION_CURSOR)
+        .map_or(code, |(before, _)| before);
+
+    let prefix: Vec<char> = before_cursor
+        .chars()
+        .rev()
+        .take_while(|char| char.is_alphanumeric() || *char == '_')
+        .collect();
+
+    prefix.into_iter().rev().collect()
+}
+
+/// Completion options for all members of the script class whose names start with the given prefix.
+fn completion_options(class: &RustScriptMetaData, prefix: &str) -> Array<Dictionary> {
+    let methods = class.methods().iter().map(|method| {
+        let insert_text = if method.arguments.is_empty() {
+            format!("{}()", method.method_name)
+        } else {
+            format!("{}(", method.method_name)
+        };
+
+        (
+            CodeCompletionKind::FUNCTION,
+            method.method_name,
+            insert_text,
+        )
+    });
+
+    let properties = class
+        .properties()
+        .iter()
+        .filter(|prop| !prop.is_category())
+        .map(|prop| {
+            (
+                CodeCompletionKind::MEMBER,
+                prop.property_name,
+                prop.property_name.to_owned(),
+            )
+        });
+
+    let signals = class.signals().iter().map(|signal| {
+        (
+            CodeCompletionKind::SIGNAL,
+            signal.name,
+            signal.name.to_owned(),
+        )
+    });
+
+    let constants = class.constants().iter().map(|constant| {
+        (
+            CodeCompletionKind::CONSTANT,
+            constant.name,
+            constant.name.to_owned(),
+        )
+    });
+
+    methods
+        .chain(properties)
+        .chain(signals)
+        .chain(constants)
+        .filter(|(_, name, _)| name.starts_with(prefix))
+        .map(|(kind, name, insert_text)| {
+            Dictionary::new().apply(|option| {
+                option.set("kind", kind);
+                option.set("display", name);
+                option.set("insert_text", insert_text);
+                option.set("location", 0);
+            })
+        })
+        .collect()
+}
+
+/// Resolves a symbol to the documentation of a member of the script class.
+fn lookup_member(
+    class: &RustScriptMetaData,
+    symbol: &str,
+) -> Option<(LookupResultType, String, Option<String>)> {
+    let class_name = class.class_name().to_string();
+
+    if symbol == class_name {
+        return Some((LookupResultType::CLASS, class_name, None));
+    }
+
+    let result_type = if class.method(symbol).is_some() {
+        LookupResultType::CLASS_METHOD
+    } else if class
+        .properties()
+        .iter()
+        .any(|prop| !prop.is_category() && prop.property_name == symbol)
+    {
+        LookupResultType::CLASS_PROPERTY
+    } else if class.signals().iter().any(|signal| signal.name == symbol) {
+        LookupResultType::CLASS_SIGNAL
+    } else if class
+        .constants()
+        .iter()
+        .any(|constant| constant.name == symbol)
+    {
+        LookupResultType::CLASS_CONSTANT
+    } else {
+        return None;
+    };
+
+    Some((result_type, class_name, Some(symbol.to_owned())))
+}
+
+/// Reloads a rust script through the engine, which re-creates all instances of the script.
+fn reload_script(script: Gd<Script>, keep_state: bool) {
+    let Ok(mut script) = script.try_cast::<RustScript>() else {
+        return;
+    };
+
+    let result = script.reload_ex().keep_state(keep_state).done();
+
+    if result != global::Error::OK {
+        global::godot_warn!(
+            "failed to reload rust script {}: {:?}",
+            script.bind().str_class_name(),
+            result
+        );
+    }
+}
+
+/// Opens the script file in the external editor that has been configured in the editor settings. Returns `None` if no
+/// external editor is configured.
+fn launch_external_editor(script: &Gd<Script>, line: i32, col: i32) -> Option<global::Error> {
+    let settings = editor_settings()?;
+
+    let exec_path: GString = settings
+        .get_setting("text_editor/external/exec_path")
+        .try_to()
+        .unwrap_or_default();
+
+    if exec_path.is_empty() {
+        return None;
+    }
+
+    let exec_flags: GString = settings
+        .get_setting("text_editor/external/exec_flags")
+        .try_to()
+        .unwrap_or_default();
+
+    let project_settings = ProjectSettings::singleton();
+    let file = project_settings
+        .globalize_path(&script.get_path())
+        .to_string();
+    let project = project_settings.globalize_path("res://").to_string();
+
+    // the engine passes zero based positions, editors expect them to start at one.
+    let line = (line + 1).max(1).to_string();
+    let col = (col + 1).max(1).to_string();
+
+    let args: PackedStringArray = exec_flags
+        .to_string()
+        .split_whitespace()
+        .map(|arg| {
+            arg.replace("{project}", &project)
+                .replace("{file}", &file)
+                .replace("{line}", &line)
+                .replace("{col}", &col)
+        })
+        .map(|arg| GString::from(arg.as_str()))
+        .collect();
+
+    let pid = Os::singleton().create_process(&exec_path, &args);
+
+    if pid == -1 {
+        show_editor_toast(
+            &format!("Failed to launch external editor \"{exec_path}\"."),
+            EditorToasterSeverity::Error,
+        );
+
+        return Some(global::Error::ERR_CANT_CREATE);
+    }
+
+    Some(global::Error::OK)
+}
+
+fn editor_settings() -> Option<Gd<EditorSettings>> {
+    if !Engine::singleton().is_editor_hint() {
+        return None;
+    }
+
+    #[cfg(before_api = "4.2")]
+    let settings = Engine::singleton()
+        .get_singleton("EditorInterface")
+        .and_then(|obj| obj.cast::<EditorInterface>().get_editor_settings())?;
+
+    #[cfg(since_api = "4.2")]
+    let settings = EditorInterface::singleton().get_editor_settings()?;
+
+    Some(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve_class_name;
+
+    #[test]
+    fn class_name_from_snake_case_file() {
+        let classes = [
+            ("Http2Client", "", "Http2Client"),
+            ("PlayerController", "", "PlayerController"),
+        ];
+
+        assert_eq!(
+            resolve_class_name("http2_client", classes.into_iter()),
+            "Http2Client"
+        );
+        assert_eq!(
+            resolve_class_name("player_controller", classes.into_iter()),
+            "PlayerController"
+        );
+    }
+
+    #[test]
+    fn class_name_from_exact_file_stem() {
+        let classes = [
+            ("HTTP2Client", "", "HTTP2Client"),
+            ("HUDOverlay", "", "HUDOverlay"),
+        ];
+
+        assert_eq!(
+            resolve_class_name("HTTP2Client", classes.into_iter()),
+            "HTTP2Client"
+        );
+        assert_eq!(
+            resolve_class_name("HUDOverlay", classes.into_iter()),
+            "HUDOverlay"
+        );
+    }
+
+    #[test]
+    fn class_name_with_prefix() {
+        let classes = [("PluginToolbar", "Plugin", "Toolbar")];
+
+        assert_eq!(
+            resolve_class_name("toolbar", classes.into_iter()),
+            "PluginToolbar"
+        );
+        assert_eq!(
+            resolve_class_name("Toolbar", classes.into_iter()),
+            "PluginToolbar"
+        );
+    }
+
+    #[test]
+    fn class_name_of_renamed_script() {
+        let classes = [("LabeledNode", "", "CustomBaseScript")];
+
+        assert_eq!(
+            resolve_class_name("custom_base_script", classes.into_iter()),
+            "LabeledNode"
+        );
+        assert_eq!(
+            resolve_class_name("CustomBaseScript", classes.into_iter()),
+            "LabeledNode"
+        );
+    }
+
+    #[test]
+    fn unknown_class_name_falls_back_to_camel_case() {
+        assert_eq!(
+            resolve_class_name("http2_client", std::iter::empty()),
+            "Http2Client"
+        );
+        assert_eq!(
+            resolve_class_name("HUD_overlay", std::iter::empty()),
+            "HUDOverlay"
+        );
     }
 }
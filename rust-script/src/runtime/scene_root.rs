@@ -0,0 +1,76 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use godot::builtin::{StringName, Variant};
+use godot::classes::{ClassDb, PackedScene, SceneState};
+use godot::global::godot_warn;
+use godot::prelude::Gd;
+
+use super::RustScript;
+
+/// Warns if the root node of a packed scene that is assigned to a `#[export(scene_root = ...)]` field does not inherit
+/// the expected class. The scene is assigned either way.
+///
+/// The expected class is either an engine class or the class name of a rust script. Script classes are not known to
+/// the `ClassDB`, so they are compared with the script that is attached to the root node instead.
+pub fn validate_scene_root(
+    script_class: &str,
+    property: &str,
+    value: &Variant,
+    root_class: &'static str,
+) {
+    let Ok(scene) = value.try_to::<Gd<PackedScene>>() else {
+        return;
+    };
+
+    let Some(state) = scene.get_state() else {
+        return;
+    };
+
+    if state.get_node_count() == 0 {
+        return;
+    }
+
+    let root_type = state.get_node_type(0);
+
+    // scenes that inherit from another scene do not store the type of their root node.
+    if root_type.is_empty() {
+        return;
+    }
+
+    let is_valid = if ClassDb::singleton().class_exists(root_class) {
+        ClassDb::singleton().is_parent_class(&root_type, root_class)
+    } else {
+        root_script_class(&state).is_some_and(|class_name| class_name == root_class)
+    };
+
+    if is_valid {
+        return;
+    }
+
+    godot_warn!(
+        "{}: scene \"{}\" assigned to property \"{}\" has a {} root node, but {} is expected",
+        script_class,
+        scene.get_path(),
+        property,
+        root_type,
+        root_class,
+    );
+}
+
+/// Class name of the rust script that is attached to the root node of a scene.
+fn root_script_class(state: &Gd<SceneState>) -> Option<String> {
+    let script_property = StringName::from("script");
+
+    let script = (0..state.get_node_property_count(0))
+        .find(|index| state.get_node_property_name(0, *index) == script_property)
+        .map(|index| state.get_node_property_value(0, index))?;
+
+    let script = script.try_to::<Gd<RustScript>>().ok()?;
+    let class_name = script.bind().str_class_name();
+
+    Some(class_name)
+}
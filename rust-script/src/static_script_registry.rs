@@ -7,9 +7,11 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
-use std::sync::{Arc, LazyLock, RwLock};
+use std::sync::{Arc, LazyLock, OnceLock, RwLock};
 
-use godot::builtin::{GString, StringName};
+use godot::builtin::{Dictionary, GString, StringName, Variant};
+use godot::classes::multiplayer_api::RpcMode;
+use godot::classes::multiplayer_peer::TransferMode;
 use godot::global::{MethodFlags, PropertyHint, PropertyUsageFlags};
 use godot::meta::{ClassName, MethodInfo, PropertyHintInfo, PropertyInfo, ToGodot};
 use godot::obj::{EngineBitfield, EngineEnum};
@@ -24,12 +26,16 @@ godot::sys::plugin_registry!(pub SCRIPT_REGISTRY: RegistryItem);
 #[macro_export]
 #[cfg(before_api = "4.4")]
 macro_rules! register_script_class {
-    ($class_name:ty, $base_name:ty, $desc:expr, $props:expr, $signals:expr) => {
+    ($class_name:ty, $base_name:ty, $prefix:expr, $icon:expr, $desc:expr, $tutorials:expr, $deprecated:expr, $experimental:expr, $tool:expr, $props:expr, $signals:expr) => {
         $crate::private_export::plugin_add! {
         SCRIPT_REGISTRY in $crate::private_export;
             $crate::private_export::RegistryItem::Entry($crate::private_export::RustScriptEntry {
-                class_name: stringify!($class_name),
-                class_name_cstr: ::std::ffi::CStr::from_bytes_with_nul(concat!(stringify!($class_name), "\0").as_bytes()).unwrap(),
+                class_name: <$class_name as $crate::GodotScript>::CLASS_NAME,
+                type_name: ::std::stringify!($class_name),
+                class_prefix: $prefix,
+                module_path: ::std::module_path!(),
+                icon_path: $icon,
+                class_name_cstr: ::std::ffi::CStr::from_bytes_with_nul($crate::private_export::concat!(<$class_name as $crate::GodotScript>::CLASS_NAME, "\0").as_bytes()).unwrap(),
                 base_type_name: <$base_name as $crate::godot::prelude::GodotClass>::class_name().to_cow_str(),
                 properties: || {
                     $props
@@ -38,7 +44,13 @@ macro_rules! register_script_class {
                     $signals
                 },
                 create_data: $crate::private_export::create_default_data_struct::<$class_name>,
+                property_defaults: <$class_name as $crate::GodotScript>::property_default_value,
+                getter_defaults: <$class_name as $crate::GodotScript>::property_getter_defaults,
                 description: $desc,
+                tutorials: $tutorials,
+                is_deprecated: $deprecated,
+                is_experimental: $experimental,
+                is_tool: $tool,
             })
         }
     };
@@ -47,11 +59,15 @@ macro_rules! register_script_class {
 #[macro_export]
 #[cfg(since_api = "4.4")]
 macro_rules! register_script_class {
-    ($class_name:ty, $base_name:ty, $desc:expr, $props:expr, $signals:expr) => {
+    ($class_name:ty, $base_name:ty, $prefix:expr, $icon:expr, $desc:expr, $tutorials:expr, $deprecated:expr, $experimental:expr, $tool:expr, $props:expr, $signals:expr) => {
         $crate::private_export::plugin_add! {
         SCRIPT_REGISTRY in $crate::private_export;
             $crate::private_export::RegistryItem::Entry($crate::private_export::RustScriptEntry {
-                class_name: stringify!($class_name),
+                class_name: <$class_name as $crate::GodotScript>::CLASS_NAME,
+                type_name: ::std::stringify!($class_name),
+                class_prefix: $prefix,
+                module_path: ::std::module_path!(),
+                icon_path: $icon,
                 base_type_name: <$base_name as $crate::godot::prelude::GodotClass>::class_name().to_cow_str(),
                 properties: || {
                     $props
@@ -60,7 +76,13 @@ macro_rules! register_script_class {
                     $signals
                 },
                 create_data: $crate::private_export::create_default_data_struct::<$class_name>,
+                property_defaults: <$class_name as $crate::GodotScript>::property_default_value,
+                getter_defaults: <$class_name as $crate::GodotScript>::property_getter_defaults,
                 description: $desc,
+                tutorials: $tutorials,
+                is_deprecated: $deprecated,
+                is_experimental: $experimental,
+                is_tool: $tool,
             })
         }
     };
@@ -68,36 +90,84 @@ macro_rules! register_script_class {
 
 #[macro_export]
 macro_rules! register_script_methods {
-    ($class_name:ty, $methods:expr) => {
+    ($class_name:ty, $methods:expr, $constants:expr) => {
         $crate::private_export::plugin_add! {
             SCRIPT_REGISTRY in $crate::private_export;
             $crate::private_export::RegistryItem::Methods($crate::private_export::RustScriptEntryMethods {
-                class_name: stringify!($class_name),
+                class_name: <$class_name as $crate::GodotScript>::CLASS_NAME,
                 methods: || {
                     $methods
                 },
+                constants: || {
+                    $constants
+                },
+                call_static: <$class_name as $crate::GodotScriptImpl>::call_static_fn,
             })
         }
     };
 }
 
+#[macro_export]
+#[cfg(before_api = "4.4")]
+macro_rules! script_class_name {
+    ($class_name:ty) => {
+        $crate::private_export::script_class_name(
+            <$class_name as $crate::GodotScript>::CLASS_NAME,
+            ::std::ffi::CStr::from_bytes_with_nul(
+                $crate::private_export::concat!(
+                    <$class_name as $crate::GodotScript>::CLASS_NAME,
+                    "\0"
+                )
+                .as_bytes(),
+            )
+            .unwrap(),
+        )
+    };
+}
+
+#[macro_export]
+#[cfg(since_api = "4.4")]
+macro_rules! script_class_name {
+    ($class_name:ty) => {
+        $crate::private_export::script_class_name(<$class_name as $crate::GodotScript>::CLASS_NAME)
+    };
+}
+
 pub struct RustScriptEntry {
     pub class_name: &'static str,
+    /// Name of the rust type. It differs from the class name if the script has been renamed.
+    pub type_name: &'static str,
+    pub class_prefix: &'static str,
+    /// Module the script has been declared in.
+    pub module_path: &'static str,
+    pub icon_path: &'static str,
     #[cfg(before_api = "4.4")]
     pub class_name_cstr: &'static std::ffi::CStr,
     pub base_type_name: Cow<'static, str>,
     pub properties: fn() -> Vec<RustScriptPropDesc>,
     pub signals: fn() -> Vec<RustScriptSignalDesc>,
     pub create_data: fn(Gd<Object>) -> Box<dyn GodotScriptObject>,
+    pub property_defaults: fn(StringName) -> Option<Variant>,
+    pub getter_defaults: fn() -> HashMap<StringName, Variant>,
     pub description: &'static str,
+    /// Title and link of the tutorials that are referenced in the class documentation.
+    pub tutorials: &'static [(&'static str, &'static str)],
+    pub is_deprecated: bool,
+    pub is_experimental: bool,
+    pub is_tool: bool,
 }
 
 #[derive(Debug)]
 pub struct RustScriptEntryMethods {
     pub class_name: &'static str,
     pub methods: fn() -> Vec<RustScriptMethodDesc>,
+    pub constants: fn() -> Vec<RustScriptConstDesc>,
+    pub call_static: StaticCallFn,
 }
 
+pub type StaticCallFn =
+    fn(StringName, &[&Variant]) -> Result<Variant, godot::sys::GDExtensionCallErrorType>;
+
 pub enum RegistryItem {
     Entry(RustScriptEntry),
     Methods(RustScriptEntryMethods),
@@ -109,6 +179,8 @@ pub struct RustScriptPropDesc {
     pub ty: VariantType,
     pub class_name: ClassName,
     pub exported: bool,
+    /// Usage flags that are applied in addition to the ones implied by `exported`.
+    pub usage: PropertyUsageFlags,
     pub hint: PropertyHint,
     pub hint_string: String,
     pub description: &'static str,
@@ -121,9 +193,13 @@ impl RustScriptPropDesc {
             class_name: self.class_name,
             property_name: self.name,
             usage: if self.exported {
-                (PropertyUsageFlags::EDITOR | PropertyUsageFlags::STORAGE).ord()
+                (PropertyUsageFlags::EDITOR
+                    | PropertyUsageFlags::STORAGE
+                    | PropertyUsageFlags::SCRIPT_VARIABLE
+                    | self.usage)
+                    .ord()
             } else {
-                PropertyUsageFlags::NONE.ord()
+                self.usage.ord()
             },
             hint: self.hint.ord(),
             hint_string: self.hint_string.clone(),
@@ -136,8 +212,12 @@ pub struct RustScriptMethodDesc {
     pub name: &'static str,
     pub return_type: RustScriptPropDesc,
     pub arguments: Box<[RustScriptPropDesc]>,
+    pub default_arguments: Box<[fn() -> Variant]>,
     pub flags: MethodFlags,
     pub description: &'static str,
+    pub is_deprecated: bool,
+    pub is_experimental: bool,
+    pub rpc: Option<RustScriptRpcDesc>,
 }
 
 impl RustScriptMethodDesc {
@@ -160,11 +240,38 @@ impl RustScriptMethodDesc {
                 .iter()
                 .map(|arg| arg.to_property_info())
                 .collect(),
+            default_arguments: self.default_arguments,
             description: self.description,
+            is_deprecated: self.is_deprecated,
+            is_experimental: self.is_experimental,
+            rpc: self.rpc,
         }
     }
 }
 
+/// Remote procedure call settings of a script method, declared with `#[rpc(...)]`.
+#[derive(Debug, Clone, Copy)]
+pub struct RustScriptRpcDesc {
+    pub mode: RpcMode,
+    pub transfer_mode: TransferMode,
+    pub call_local: bool,
+    pub channel: i32,
+}
+
+impl RustScriptRpcDesc {
+    /// The configuration in the format the multiplayer API expects, the same as for the `@rpc` annotation in GDScript.
+    pub fn to_dictionary(&self) -> Dictionary {
+        let mut config = Dictionary::new();
+
+        config.set("rpc_mode", self.mode);
+        config.set("transfer_mode", self.transfer_mode);
+        config.set("call_local", self.call_local);
+        config.set("channel", self.channel);
+
+        config
+    }
+}
+
 pub struct RustScriptSignalDesc {
     pub name: &'static str,
     pub arguments: Box<[RustScriptPropDesc]>,
@@ -185,17 +292,28 @@ impl From<RustScriptSignalDesc> for RustScriptSignalInfo {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct RustScriptConstDesc {
+    pub name: &'static str,
+    pub value: fn() -> Variant,
+}
+
 pub fn create_default_data_struct<T: GodotScript + GodotScriptObject + 'static>(
     base: Gd<Object>,
 ) -> Box<dyn GodotScriptObject> {
     Box::new(T::default_with_base(base))
 }
 
+/// Assembles the metadata of all scripts that have been declared inside the given root module.
 pub fn assemble_metadata<'a>(
     items: impl Iterator<Item = &'a RegistryItem> + 'a,
+    root_module: &str,
 ) -> Vec<RustScriptMetaData> {
     let (entries, methods): (Vec<_>, Vec<_>) = items
         .map(|item| match item {
+            RegistryItem::Entry(entry) if !is_in_module(entry.module_path, root_module) => {
+                (None, None)
+            }
             RegistryItem::Entry(entry) => (Some(entry), None),
             RegistryItem::Methods(methods) => (None, Some((methods.class_name, methods))),
         })
@@ -212,6 +330,14 @@ pub fn assemble_metadata<'a>(
                 .map(|prop| prop.to_property_info())
                 .collect();
 
+            let constants = methods
+                .get(class.class_name)
+                .into_iter()
+                .flat_map(|entry| (entry.constants)())
+                .collect();
+
+            let call_static = methods.get(class.class_name).map(|entry| entry.call_static);
+
             let methods = methods
                 .get(class.class_name)
                 .into_iter()
@@ -230,23 +356,50 @@ pub fn assemble_metadata<'a>(
             let signals = (class.signals)().into_iter().map(Into::into).collect();
 
             let create_data: Box<dyn CreateScriptInstanceData> = Box::new(class.create_data);
+            let property_defaults = class.property_defaults;
+            let getter_defaults = class.getter_defaults;
             let description = class.description;
+            let tutorials = class.tutorials;
+            let is_deprecated = class.is_deprecated;
+            let is_experimental = class.is_experimental;
+            let is_tool = class.is_tool;
+            let class_prefix = class.class_prefix;
+            let module_path = class.module_path;
+            let icon_path = class.icon_path;
 
             RustScriptMetaData::new(
                 class.class_name,
                 #[cfg(before_api = "4.4")]
                 class.class_name_cstr,
+                class.type_name,
+                class_prefix,
+                module_path,
+                icon_path,
                 class.base_type_name.as_ref().into(),
                 props,
                 methods,
                 signals,
+                constants,
+                call_static,
                 create_data,
+                property_defaults,
+                getter_defaults,
                 description,
+                tutorials,
+                is_deprecated,
+                is_experimental,
+                is_tool,
             )
         })
         .collect()
 }
 
+fn is_in_module(module_path: &str, root_module: &str) -> bool {
+    module_path
+        .strip_prefix(root_module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct RustScriptPropertyInfo {
@@ -259,6 +412,13 @@ pub struct RustScriptPropertyInfo {
     pub description: &'static str,
 }
 
+impl RustScriptPropertyInfo {
+    /// Categories only structure the inspector and are not members of the script.
+    pub fn is_category(&self) -> bool {
+        self.usage & PropertyUsageFlags::CATEGORY.ord() != 0
+    }
+}
+
 impl From<&RustScriptPropertyInfo> for PropertyInfo {
     fn from(value: &RustScriptPropertyInfo) -> Self {
         Self {
@@ -285,8 +445,13 @@ pub struct RustScriptMethodInfo {
     pub class_name_cstr: &'static std::ffi::CStr,
     pub return_type: RustScriptPropertyInfo,
     pub arguments: Box<[RustScriptPropertyInfo]>,
+    /// Default values of the trailing arguments of the method.
+    pub default_arguments: Box<[fn() -> Variant]>,
     pub flags: u64,
     pub description: &'static str,
+    pub is_deprecated: bool,
+    pub is_experimental: bool,
+    pub rpc: Option<RustScriptRpcDesc>,
 }
 
 impl From<&RustScriptMethodInfo> for MethodInfo {
@@ -301,7 +466,11 @@ impl From<&RustScriptMethodInfo> for MethodInfo {
             ),
             return_type: (&value.return_type).into(),
             arguments: value.arguments.iter().map(|arg| arg.into()).collect(),
-            default_arguments: vec![],
+            default_arguments: value
+                .default_arguments
+                .iter()
+                .map(|default| default())
+                .collect(),
             flags: MethodFlags::try_from_ord(value.flags).unwrap_or(MethodFlags::DEFAULT),
         }
     }
@@ -340,12 +509,28 @@ impl From<&RustScriptSignalInfo> for MethodInfo {
 #[derive(Debug, Clone)]
 pub struct RustScriptMetaData {
     pub(crate) class_name: ClassName,
+    pub(crate) type_name: &'static str,
+    pub(crate) class_prefix: &'static str,
+    pub(crate) module_path: &'static str,
+    pub(crate) icon_path: &'static str,
     pub(crate) base_type_name: StringName,
     pub(crate) properties: Box<[RustScriptPropertyInfo]>,
     pub(crate) methods: Box<[RustScriptMethodInfo]>,
+    pub(crate) method_indices: HashMap<&'static str, usize>,
     pub(crate) signals: Box<[RustScriptSignalInfo]>,
+    pub(crate) constants: Box<[RustScriptConstDesc]>,
+    pub(crate) call_static: Option<StaticCallFn>,
     pub(crate) create_data: Arc<dyn CreateScriptInstanceData>,
+    pub(crate) property_defaults: fn(StringName) -> Option<Variant>,
+    pub(crate) getter_defaults: fn() -> HashMap<StringName, Variant>,
+    /// Default values of the properties with a custom getter. Evaluated on first use, since it requires a default
+    /// instance of the script.
+    pub(crate) getter_default_values: OnceLock<HashMap<StringName, Variant>>,
     pub(crate) description: &'static str,
+    pub(crate) tutorials: &'static [(&'static str, &'static str)],
+    pub(crate) is_deprecated: bool,
+    pub(crate) is_experimental: bool,
+    pub(crate) is_tool: bool,
 }
 
 impl RustScriptMetaData {
@@ -353,25 +538,57 @@ impl RustScriptMetaData {
     pub fn new(
         class_name: &'static str,
         #[cfg(before_api = "4.4")] class_name_cstr: &'static std::ffi::CStr,
+        type_name: &'static str,
+        class_prefix: &'static str,
+        module_path: &'static str,
+        icon_path: &'static str,
         base_type_name: StringName,
         properties: Box<[RustScriptPropertyInfo]>,
         methods: Box<[RustScriptMethodInfo]>,
         signals: Box<[RustScriptSignalInfo]>,
+        constants: Box<[RustScriptConstDesc]>,
+        call_static: Option<StaticCallFn>,
         create_data: Box<dyn CreateScriptInstanceData>,
+        property_defaults: fn(StringName) -> Option<Variant>,
+        getter_defaults: fn() -> HashMap<StringName, Variant>,
         description: &'static str,
+        tutorials: &'static [(&'static str, &'static str)],
+        is_deprecated: bool,
+        is_experimental: bool,
+        is_tool: bool,
     ) -> Self {
+        let method_indices = methods
+            .iter()
+            .enumerate()
+            .map(|(index, method)| (method.method_name, index))
+            .collect();
+
         Self {
             #[cfg(before_api = "4.4")]
             class_name: ClassName::new_script(class_name, class_name_cstr),
 
             #[cfg(since_api = "4.4")]
             class_name: ClassName::new_script(class_name),
+            type_name,
+            class_prefix,
+            module_path,
+            icon_path,
             base_type_name,
             properties,
             methods,
+            method_indices,
             signals,
+            constants,
+            call_static,
             create_data: Arc::from(create_data),
+            property_defaults,
+            getter_defaults,
+            getter_default_values: OnceLock::new(),
             description,
+            tutorials,
+            is_deprecated,
+            is_experimental,
+            is_tool,
         }
     }
 }
@@ -381,6 +598,28 @@ impl RustScriptMetaData {
         self.class_name
     }
 
+    /// Name of the rust type that implements the script. Renamed scripts are still found by the name of their type.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Prefix that has been prepended to the name of the script class. The script file is named without it.
+    pub fn class_prefix(&self) -> &'static str {
+        self.class_prefix
+    }
+
+    /// Module the script has been declared in.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// Resource path of the icon that represents the script class in the editor, if the class has one.
+    pub fn icon_path(&self) -> Option<&'static str> {
+        (!self.icon_path.is_empty()).then_some(self.icon_path)
+    }
+
+    /// Name of the engine or extension class the script has to be attached to. For custom rust classes, this is the
+    /// name the class has been registered with in the `ClassDB`.
     pub fn base_type_name(&self) -> StringName {
         self.base_type_name.clone()
     }
@@ -397,13 +636,103 @@ impl RustScriptMetaData {
         &self.methods
     }
 
+    /// Position of a method in [`Self::methods`].
+    pub fn method_index(&self, name: &str) -> Option<usize> {
+        self.method_indices.get(name).copied()
+    }
+
+    pub fn method(&self, name: &str) -> Option<&RustScriptMethodInfo> {
+        self.method_index(name).map(|index| &self.methods[index])
+    }
+
     pub fn signals(&self) -> &[RustScriptSignalInfo] {
         &self.signals
     }
 
+    pub fn constants(&self) -> &[RustScriptConstDesc] {
+        &self.constants
+    }
+
+    /// RPC configuration of all methods that are declared with `#[rpc(...)]`, keyed by the method name.
+    pub fn rpc_config(&self) -> Dictionary {
+        self.methods
+            .iter()
+            .filter_map(|method| Some((method.method_name, method.rpc?.to_dictionary())))
+            .collect()
+    }
+
+    pub fn has_static_method(&self, name: &str) -> bool {
+        self.method(name)
+            .is_some_and(|method| method.flags & MethodFlags::STATIC.ord() != 0)
+    }
+
+    pub fn call_static(
+        &self,
+        name: StringName,
+        args: &[&Variant],
+    ) -> Result<Variant, godot::sys::GDExtensionCallErrorType> {
+        let call_static = self
+            .call_static
+            .ok_or(godot::sys::GDEXTENSION_CALL_ERROR_INVALID_METHOD)?;
+
+        call_static(name, args)
+    }
+
+    pub fn property_default_value(&self, name: StringName) -> Option<Variant> {
+        if let Some(value) = (self.property_defaults)(name.clone()) {
+            return Some(value);
+        }
+
+        self.getter_default_values
+            .get_or_init(self.getter_defaults)
+            .get(&name)
+            .cloned()
+    }
+
+    /// First paragraph of the class documentation, up to the first blank line.
+    pub fn brief_description(&self) -> &'static str {
+        split_description(self.description).0
+    }
+
+    /// Class documentation following the brief description.
     pub fn description(&self) -> &'static str {
-        self.description
+        split_description(self.description).1
+    }
+
+    /// Title and link of the tutorials that are referenced in the class documentation.
+    pub fn tutorials(&self) -> &'static [(&'static str, &'static str)] {
+        self.tutorials
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        self.is_deprecated
+    }
+
+    pub fn is_experimental(&self) -> bool {
+        self.is_experimental
+    }
+
+    /// Tool scripts are instantiated and executed inside the editor.
+    pub fn is_tool(&self) -> bool {
+        self.is_tool
+    }
+}
+
+fn split_description(description: &'static str) -> (&'static str, &'static str) {
+    let mut offset = 0;
+
+    for line in description.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            return (
+                description[..offset].trim(),
+                &description[offset + line.len()..],
+            );
+        }
+
+        offset += line.len();
     }
+
+    (description.trim(), "")
 }
 
 pub trait CreateScriptInstanceData: Sync + Send + Debug {
@@ -422,6 +751,18 @@ where
 static DYNAMIC_INDEX_BY_CLASS_NAME: LazyLock<RwLock<HashMap<&'static str, ClassName>>> =
     LazyLock::new(RwLock::default);
 
+/// Class name of a script class, used for script typed arguments and return values of script methods.
+pub fn script_class_name(
+    class_name: &'static str,
+    #[cfg(before_api = "4.4")] class_name_cstr: &'static std::ffi::CStr,
+) -> ClassName {
+    ClassName::new_script(
+        class_name,
+        #[cfg(before_api = "4.4")]
+        class_name_cstr,
+    )
+}
+
 trait ClassNameExtension {
     #[cfg(before_api = "4.4")]
     fn new_script(str: &'static str, cstr: &'static std::ffi::CStr) -> Self;
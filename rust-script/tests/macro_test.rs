@@ -4,6 +4,8 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use godot::init::InitLevel;
+
 #[test]
 fn verify_macros() {
     let _ = || {
@@ -13,4 +15,20 @@ fn verify_macros() {
     let _ = || {
         godot_rust_script::deinit!();
     };
+
+    let _ = |level: InitLevel| {
+        godot_rust_script::init!(tests_scripts_lib, level, InitLevel::Servers);
+    };
+
+    let _ = |level: InitLevel| {
+        godot_rust_script::deinit!(level, InitLevel::Servers);
+    };
+
+    let _ = || {
+        godot_rust_script::init!([tests_scripts_lib]);
+    };
+
+    let _ = |level: InitLevel| {
+        godot_rust_script::init!([tests_scripts_lib], level, InitLevel::Servers);
+    };
 }
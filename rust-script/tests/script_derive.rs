@@ -4,22 +4,65 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use godot::builtin::{Array, GString};
-use godot::classes::{Node, Node3D};
-use godot::obj::{Gd, NewAlloc};
-use godot_rust_script::{godot_script_impl, Context, GodotScript, GodotScriptEnum, Signal};
+use std::collections::HashSet;
+#[cfg(since_api = "4.2")]
+use std::sync::{Arc, Mutex};
+
+use godot::builtin::{
+    Aabb, Array, Callable, Color, GString, NodePath, PackedStringArray, PackedVector3Array, Rid,
+    StringName, Transform3D, Variant, VariantArray,
+};
+use godot::classes::object::ConnectFlags;
+use godot::classes::{Node, Node3D, PackedScene, Resource};
+use godot::meta::{PropertyInfo, ToGodot};
+use godot::obj::{Base, Gd, NewAlloc};
+use godot::register::GodotClass;
+use godot_rust_script::{
+    godot_script_impl, script_instance_exists, Context, GodotScript, GodotScriptBindError,
+    GodotScriptCallError, GodotScriptCastError, GodotScriptEnum, GodotScriptExport,
+    GodotScriptImpl, IsScript, OnEditor, RsRef, RsRefArray, ScriptEnumArray, ScriptSignal, Signal,
+};
 
 #[derive(Debug, Default, GodotScriptEnum)]
 #[script_enum(export)]
 pub enum ScriptEnum {
+    /// The first option.
     #[default]
     One,
+    /// The second option.
     Two,
     Three,
 }
 
+#[derive(Debug, Default, PartialEq, GodotScriptEnum)]
+#[script_enum(export)]
+pub enum Quality {
+    #[default]
+    Low = 1,
+    Medium,
+    High = 10,
+    Ultra,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, GodotScriptEnum)]
+#[script_enum(export, bitflags)]
+pub enum Permission {
+    Read,
+    Write,
+    Execute,
+}
+
+#[derive(Debug, Default, PartialEq, GodotScriptEnum)]
+#[script_enum(export, repr = i64)]
+pub enum Layer {
+    #[default]
+    Background = -1,
+    World = 1024,
+    Overlay = 1_099_511_627_776,
+}
+
 #[derive(GodotScript, Debug)]
-#[script(base = Node)]
+#[script(base = Node, on_validate = Self::clamp_values)]
 struct TestScript {
     pub property_a: GString,
 
@@ -35,31 +78,181 @@ struct TestScript {
     #[signal]
     pub ready: Signal<(u32, u32)>,
 
+    #[signal]
+    pub labeled: Signal<(RsRef<CustomBaseScript>, GString)>,
+
+    /// Declared by the `Node` base class.
+    #[signal(base)]
+    pub tree_exiting: Signal<()>,
+
+    #[signal]
+    pub path_changed: Signal<PackedVector3Array>,
+
+    #[signal]
+    pub moved: Signal<(Transform3D, Aabb)>,
+
+    #[signal]
+    pub recolored: Signal<Color>,
+
+    #[signal]
+    pub resource_freed: Signal<Rid>,
+
     pub node_prop: Option<Gd<Node3D>>,
 
+    #[export_category(name = "Nodes")]
     #[export(ty = "Decal")]
     pub node_prop_2: Option<Gd<Node3D>>,
 
+    #[export]
+    pub enum_list: ScriptEnumArray<ScriptEnum>,
+
+    #[export]
+    pub labeled_nodes: RsRefArray<CustomBaseScript>,
+
+    #[export]
+    pub target: OnEditor<Gd<Node3D>>,
+
+    #[export]
+    pub permissions: PermissionFlags,
+
+    #[export(ty = ["Texture2D", "ImageTexture"])]
+    pub icon: Option<Gd<Resource>>,
+
+    #[export(duplicate = never)]
+    pub shared_resource: Option<Gd<Resource>>,
+
+    #[export(scene_root = "Node3D")]
+    pub level: Option<Gd<PackedScene>>,
+
+    #[export(script = CustomBaseScript)]
+    pub labeled_node: Option<Gd<Node>>,
+
     #[export]
     pub node_array: Array<Gd<Node3D>>,
 
     #[export(range(min = 0.0, max = 10.0))]
     pub int_range: u32,
 
+    #[export(range(min = 0.0, max = 1.0))]
+    pub float_range: f32,
+
+    #[export(range(min = 0.0, max = 100.0, step = 0.5, or_greater, suffix = "m"))]
+    pub distance: f64,
+
     #[export]
     pub custom_enum: ScriptEnum,
 
+    #[export(multiline)]
+    pub notes: GString,
+
+    #[export(file, extensions = ["png", ".json"])]
+    pub data_file: GString,
+
+    #[export(global_file, extensions = ["exe"])]
+    pub tool_path: GString,
+
+    #[export(color_no_alpha)]
+    #[prop(default = Color::WHITE)]
+    pub tint: Color,
+
+    #[export(range(min = 0.0, max = 100.0, step = 1.0))]
+    #[prop(default = Self::MAX_HEALTH)]
+    pub health: i64,
+
+    #[export(readonly)]
+    pub spawned_count: i64,
+
     base: Gd<<Self as GodotScript>::Base>,
 }
 
+/// Fallible methods can return an alias of `Result`.
+type LevelResult = Result<i64, String>;
+
 #[godot_script_impl]
 impl TestScript {
+    #[constant]
+    pub const MAX_HEALTH: i64 = 100;
+
     pub fn _init(&self) {}
 
     pub fn record(&mut self, value: u8) -> bool {
         value > 2
     }
 
+    #[rpc(mode = any_peer, transfer = unreliable_ordered, call_local, channel = 1)]
+    pub fn sync_health(&mut self, health: i64) {
+        self.health = health;
+    }
+
+    pub fn spawn(&mut self, mut count: i64, #[default(1)] step: i64) -> i64 {
+        count = count.max(0);
+
+        count * step
+    }
+
+    fn clamp_values(&mut self) {
+        self.editor_prop = self.editor_prop.min(100);
+    }
+
+    pub fn child_count(&self, ctx: Context<Self>, #[default(false)] include_internal: bool) -> i32 {
+        ctx.base()
+            .get_child_count_ex()
+            .include_internal(include_internal)
+            .done()
+    }
+
+    pub fn log(&self, level: i64, rest: &[&Variant]) -> i64 {
+        if level > 0 {
+            godot::global::godot_print!("{level}: {rest:?}");
+        }
+
+        rest.len() as i64
+    }
+
+    pub fn has_sibling(&self, name: GString, mut ctx: Context<Self>) -> bool {
+        let path = NodePath::from(format!("../{name}").as_str());
+
+        ctx.get_tree().is_some() && ctx.get_node(&path).is_some()
+    }
+
+    pub fn has_labeled_child(&mut self, name: GString, mut ctx: Context<Self>) -> bool {
+        let path = NodePath::from(&name);
+
+        ctx.get_script_node::<CustomBaseScript>(self, &path)
+            .is_some_and(|child| child.bind().is_ok_and(|child| !child.label.is_empty()))
+    }
+
+    pub fn base_name(&self, ctx: Context<Self>) -> String {
+        ctx.base().get_name().to_string()
+    }
+
+    pub fn parse_level(&self, input: GString) -> Result<i64, String> {
+        input
+            .to_string()
+            .parse::<i64>()
+            .map_err(|err| format!("invalid level: {err}"))
+    }
+
+    pub fn checked_level(&self, level: i64) -> LevelResult {
+        if level < 0 {
+            return Err(format!("negative level: {level}"));
+        }
+
+        Ok(level)
+    }
+
+    pub fn forward_label(&self, target: RsRef<CustomBaseScript>) -> RsRef<CustomBaseScript> {
+        target
+    }
+
+    pub fn long_task(&self, mut ctx: Context<Self>) -> godot::builtin::Signal {
+        ctx.defer_signal("long_task_done")
+    }
+
+    pub fn version() -> GString {
+        GString::from("1.0")
+    }
+
     pub fn action(&mut self, input: GString, mut ctx: Context<Self>) -> bool {
         let result = input.len() > 2;
         let mut base = self.base.clone();
@@ -72,6 +265,683 @@ impl TestScript {
             base.set_owner(&Node::new_alloc());
         });
 
+        ctx.emit_signal(self, "changed", &[]);
+        ctx.call_deferred(self, "record", &[3.to_variant()]);
+
         result
     }
 }
+
+#[derive(GodotClass)]
+#[class(base = Node, init)]
+struct CustomBase {
+    base: Base<Node>,
+}
+
+#[derive(GodotScript, Debug)]
+#[script(
+    base = CustomBase,
+    rename = "LabeledNode",
+    icon = "res://icons/labeled_node.svg",
+    experimental,
+    dynamic_properties = Self::label_properties,
+    get_fallback = Self::get_label_property,
+    set_fallback = Self::set_label_property,
+    configuration_warnings = Self::label_warnings,
+    to_string = Self::describe
+)]
+struct CustomBaseScript {
+    pub label: GString,
+
+    label_color: Color,
+
+    base: Gd<<Self as GodotScript>::Base>,
+}
+
+#[godot_script_impl]
+impl CustomBaseScript {
+    pub fn has_label(&self) -> bool {
+        !self.label.is_empty()
+    }
+
+    fn label_properties(&self) -> Option<Vec<PropertyInfo>> {
+        self.has_label()
+            .then(|| vec![PropertyInfo::new_export::<Color>("label_color")])
+    }
+
+    fn get_label_property(&self, name: StringName) -> Option<Variant> {
+        (name == StringName::from("label_color")).then(|| self.label_color.to_variant())
+    }
+
+    fn set_label_property(&mut self, name: StringName, value: Variant) -> bool {
+        if name != StringName::from("label_color") {
+            return false;
+        }
+
+        match value.try_to() {
+            Ok(color) => {
+                self.label_color = color;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("LabeledNode({})", self.label)
+    }
+
+    fn label_warnings(&self) -> PackedStringArray {
+        let mut warnings = PackedStringArray::new();
+
+        if !self.has_label() {
+            warnings.push("label is empty");
+        }
+
+        warnings
+    }
+}
+
+/// Helper that runs inside the editor.
+///
+/// @tutorial(Editor plugins): https://docs.godotengine.org/en/stable/tutorials/plugins/editor/index.html
+/// @experimental
+#[derive(GodotScript, Debug)]
+#[script(prefix = "Plugin", tool)]
+struct PrefixedScript {
+    base: Gd<<Self as GodotScript>::Base>,
+}
+
+#[godot_script_impl]
+impl PrefixedScript {
+    /// Refreshes the plugin state.
+    ///
+    /// @deprecated: the state is refreshed automatically.
+    pub fn refresh(&self) {}
+}
+
+type Elevation = f32;
+
+/// The base field may hold an ancestor of the declared base class.
+#[derive(GodotScript, Debug)]
+#[script(base = Node3D)]
+struct SpatialScript {
+    #[export(range(min = 0.0, max = 50.0))]
+    pub elevation: Elevation,
+
+    #[export(exp_easing = [])]
+    pub falloff: Elevation,
+
+    base: Gd<Node>,
+}
+
+#[godot_script_impl]
+impl SpatialScript {}
+
+/// Field types that reach the derive wrapped in invisible groups or parentheses.
+#[allow(unused_parens)]
+mod wrapped_field_types {
+    use godot::classes::Node3D;
+    use godot::obj::Gd;
+    use godot_rust_script::{godot_script_impl, GodotScript};
+
+    use super::Elevation;
+
+    /// Types passed through a `ty` fragment are wrapped in an invisible group.
+    macro_rules! terrain_script {
+        ($height:ty) => {
+            #[derive(GodotScript, Debug)]
+            #[script(base = Node3D)]
+            struct TerrainScript {
+                #[export(range(min = 0.0, max = 50.0))]
+                pub height: $height,
+
+                #[export]
+                pub roughness: (f32),
+
+                base: Gd<Node3D>,
+            }
+
+            #[godot_script_impl]
+            impl TerrainScript {}
+        };
+    }
+
+    terrain_script!(Elevation);
+}
+
+#[test]
+fn verify_property_default_value() {
+    let _ = || {
+        let default = TestScript::property_default_value(StringName::from("tint"));
+
+        assert_eq!(default, Some(Color::WHITE.to_variant()));
+
+        let default = TestScript::property_default_value(StringName::from("health"));
+
+        assert_eq!(default, Some(TestScript::MAX_HEALTH.to_variant()));
+
+        let default = TestScript::property_default_value(StringName::from("editor_prop"));
+
+        assert_eq!(default, Some(0u16.to_variant()));
+
+        let default = TestScript::property_default_value(StringName::from("property_a"));
+
+        assert_eq!(default, None);
+    };
+}
+
+#[test]
+fn verify_static_method_dispatch() {
+    let _ = || {
+        let version = TestScript::call_static_fn(StringName::from("version"), &[]);
+
+        assert_eq!(version, Ok(GString::from("1.0").to_variant()));
+    };
+}
+
+#[test]
+fn verify_custom_base_class() {
+    let _ = |node: Gd<CustomBase>| {
+        let mut script = CustomBaseScript::default_with_base(node.clone().upcast());
+
+        assert_eq!(script.base.instance_id(), node.instance_id());
+        assert!(!script.has_label());
+
+        script.label = GString::from("custom");
+
+        assert!(script.has_label());
+    };
+}
+
+#[test]
+fn verify_renamed_class_name() {
+    assert_eq!(CustomBaseScript::CLASS_NAME, "LabeledNode");
+    assert_eq!(TestScript::CLASS_NAME, "TestScript");
+}
+
+#[test]
+fn verify_prefixed_class_name() {
+    assert_eq!(PrefixedScript::CLASS_NAME, "PluginPrefixedScript");
+}
+
+#[test]
+fn verify_property_state_round_trip() {
+    let _ = |script: &mut TestScript| {
+        let state = script.property_state();
+
+        for (name, value) in state {
+            assert!(script.set(name.clone(), value.clone()));
+            assert_eq!(script.get(name), Some(value));
+        }
+    };
+}
+
+#[test]
+fn verify_reload_state_keeps_unassigned_on_editor() {
+    let _ = |script: &mut TestScript| {
+        let name = StringName::from("target");
+        let state = script.property_state();
+        let was_init = script.target.is_init();
+
+        // hot reloads restore every entry of the property state on the new instance.
+        assert!(state.contains_key(&name));
+        script.set(name.clone(), state[&name].clone());
+
+        assert_eq!(script.target.is_init(), was_init);
+    };
+}
+
+#[cfg(since_api = "4.2")]
+#[test]
+fn verify_signal_connect_fn() {
+    let _ = |script: &mut TestScript| {
+        script
+            .labeled
+            .connect_fn(|(node, label): (RsRef<CustomBaseScript>, GString)| {
+                assert_eq!(node.get_name().to_string(), label.to_string());
+            })
+            .unwrap();
+    };
+}
+
+#[test]
+fn verify_signal_connection_state() {
+    let _ = |script: &mut TestScript, callable: Callable| {
+        script
+            .labeled
+            .connect_flags(callable.clone(), ConnectFlags::ONE_SHOT)
+            .unwrap();
+
+        assert!(script.labeled.is_connected(&callable));
+
+        script.labeled.disconnect(&callable);
+
+        assert!(!script.labeled.is_connected(&callable));
+    };
+}
+
+#[test]
+fn verify_script_ref_array_round_trip() {
+    let _ = |nodes: Array<Gd<CustomBase>>| {
+        let scripts = RsRef::<CustomBaseScript>::try_from_array(&nodes).unwrap();
+        let array = RsRef::to_array(&scripts);
+
+        assert_eq!(array.len(), nodes.len());
+
+        let variants: VariantArray = scripts.iter().map(ToGodot::to_variant).collect();
+        let scripts = RsRef::<CustomBaseScript>::try_from_variant_array(&variants).unwrap();
+
+        assert_eq!(scripts.len(), nodes.len());
+    };
+}
+
+#[cfg(since_api = "4.2")]
+#[test]
+fn verify_signal_emit_deferred() {
+    let _ = |script: &mut TestScript| {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let handler_received = Arc::clone(&received);
+
+        script
+            .ready
+            .connect_fn(move |args: (u32, u32)| handler_received.lock().unwrap().push(args))
+            .unwrap();
+
+        script.ready.emit_deferred((1, 2));
+
+        // the emission stays queued until the engine flushes its deferred calls.
+        assert!(received.lock().unwrap().is_empty());
+
+        script.ready.emit((3, 4));
+
+        assert_eq!(*received.lock().unwrap(), [(3, 4)]);
+    };
+}
+
+#[cfg(since_api = "4.2")]
+#[test]
+fn verify_signal_emit_to() {
+    let _ = |script: &mut TestScript| {
+        let connected = Arc::new(Mutex::new(Vec::new()));
+        let connected_received = Arc::clone(&connected);
+
+        script
+            .ready
+            .connect_fn(move |args: (u32, u32)| connected_received.lock().unwrap().push(args))
+            .unwrap();
+
+        let targeted = Arc::new(Mutex::new(Vec::new()));
+        let target_received = Arc::clone(&targeted);
+        let target = Callable::from_fn("target", move |args| {
+            target_received
+                .lock()
+                .unwrap()
+                .push((args[0].to::<u32>(), args[1].to::<u32>()));
+
+            Ok(Variant::nil())
+        });
+
+        script.ready.emit_to(&target, (1, 2));
+
+        assert_eq!(*targeted.lock().unwrap(), [(1, 2)]);
+        assert!(connected.lock().unwrap().is_empty());
+    };
+}
+
+#[cfg(since_api = "4.2")]
+#[test]
+fn verify_signal_emit_through_script_ref() {
+    let _ = |mut script: RsRef<TestScript>| {
+        let events = Arc::new(Mutex::new(Vec::<String>::new()));
+
+        {
+            let mut data = script.bind_mut().unwrap();
+
+            let changed_events = Arc::clone(&events);
+            data.changed
+                .connect_fn(move |()| changed_events.lock().unwrap().push("changed".into()))
+                .unwrap();
+
+            let ready_events = Arc::clone(&events);
+            data.ready
+                .connect_fn(move |(a, b): (u32, u32)| {
+                    ready_events.lock().unwrap().push(format!("ready {a} {b}"))
+                })
+                .unwrap();
+
+            let recolored_events = Arc::clone(&events);
+            data.recolored
+                .connect_fn(move |color: Color| {
+                    recolored_events
+                        .lock()
+                        .unwrap()
+                        .push(format!("recolored {color:?}"))
+                })
+                .unwrap();
+
+            let exiting_events = Arc::clone(&events);
+            data.tree_exiting
+                .connect_fn(move |()| exiting_events.lock().unwrap().push("tree_exiting".into()))
+                .unwrap();
+        }
+
+        script.emit_changed();
+        script.emit_ready(1, 2);
+        script.emit_recolored(Color::WHITE);
+        script.emit_tree_exiting();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "changed".to_owned(),
+                "ready 1 2".to_owned(),
+                format!("recolored {:?}", Color::WHITE),
+                "tree_exiting".to_owned(),
+            ]
+        );
+    };
+}
+
+#[test]
+fn verify_script_ref_bind() {
+    let _ = |mut script: RsRef<TestScript>| {
+        let editor_prop = script.bind().unwrap().editor_prop;
+
+        script.bind_mut().unwrap().editor_prop = editor_prop + 1;
+
+        assert_eq!(script.bind().unwrap().editor_prop, editor_prop + 1);
+
+        let mut other = script.clone();
+        let guard = script.bind().unwrap();
+
+        assert!(matches!(
+            other.bind_mut(),
+            Err(GodotScriptBindError::AlreadyBorrowed)
+        ));
+
+        drop(guard);
+    };
+}
+
+#[test]
+fn verify_script_instance_exists() {
+    let _ = |node: Gd<Node>| {
+        let exists = script_instance_exists(TestScript::CLASS_NAME, node.instance_id());
+
+        assert!(exists);
+    };
+}
+
+#[test]
+fn verify_script_to_string() {
+    let _ = |node: Gd<Node>, script: &TestScript| {
+        assert_eq!(node.to_string(), GodotScript::to_string(script));
+    };
+}
+
+#[test]
+fn verify_default_argument_dispatch() {
+    let _ = |script: RsRef<TestScript>| {
+        assert_eq!(
+            script.try_call("spawn", &[3.to_variant()]).ok(),
+            Some(3.to_variant())
+        );
+        assert_eq!(
+            script
+                .try_call("spawn", &[3.to_variant(), 2.to_variant()])
+                .ok(),
+            Some(6.to_variant())
+        );
+        assert!(matches!(
+            script.try_call("spawn", &[]),
+            Err(GodotScriptCallError::TooFewArguments(_, 1, 0))
+        ));
+        assert!(matches!(
+            script.try_call("despawn", &[]),
+            Err(GodotScriptCallError::InvalidMethod("TestScript", _))
+        ));
+        assert_eq!(
+            script.try_call("child_count", &[]).ok(),
+            Some(script.get_child_count().to_variant())
+        );
+    };
+}
+
+#[test]
+fn verify_fallible_method_interface() {
+    let _ = |script: RsRef<TestScript>| {
+        let level: Result<i64, GodotScriptCallError> = script.parse_level("3".into());
+
+        assert_eq!(level.ok(), Some(3));
+        assert_eq!(script.checked_level(2).ok(), Some(2));
+        assert!(matches!(
+            script.checked_level(-1),
+            Err(GodotScriptCallError::Call(_))
+        ));
+    };
+}
+
+#[test]
+fn verify_script_enum_array_hint() {
+    let hint_string = <ScriptEnumArray<ScriptEnum> as GodotScriptExport>::hint_string(None, None);
+
+    assert_eq!(hint_string, "2/2:One:0,Two:1,Three:2");
+}
+
+#[test]
+fn verify_script_enum_discriminants() {
+    assert_eq!(u8::from(&Quality::Medium), 2);
+    assert_eq!(u8::from(&Quality::Ultra), 11);
+    assert_eq!(Quality::try_from(10).ok(), Some(Quality::High));
+    assert!(Quality::try_from(3).is_err());
+    assert_eq!(
+        <Quality as GodotScriptExport>::hint_string(None, None),
+        "Low:1,Medium:2,High:10,Ultra:11"
+    );
+}
+
+#[test]
+fn verify_script_enum_i64_repr() {
+    assert_eq!(i64::from(&Layer::Background), -1);
+    assert_eq!(Layer::Overlay.to_godot(), 1_099_511_627_776);
+    assert_eq!(Layer::try_from(1024).ok(), Some(Layer::World));
+    assert_eq!(
+        <Layer as GodotScriptExport>::hint_string(None, None),
+        "Background:-1,World:1024,Overlay:1099511627776"
+    );
+}
+
+#[test]
+fn verify_script_enum_bitflags() {
+    let flags = Permission::Read | Permission::Execute;
+
+    assert_eq!(flags.bits(), 0b101);
+    assert!(flags.contains(Permission::Execute));
+    assert!(!flags.contains(Permission::Write));
+    assert_eq!((flags & Permission::Read).bits(), 1);
+    assert_eq!(
+        PermissionFlags::try_from(0b111)
+            .map(|flags| flags.bits())
+            .ok(),
+        Some(7)
+    );
+    assert!(PermissionFlags::try_from(0b1000).is_err());
+    assert_eq!(
+        <PermissionFlags as GodotScriptExport>::hint_string(None, None),
+        "Read,Write,Execute"
+    );
+}
+
+#[test]
+fn verify_on_editor_state() {
+    let _ = |script: &mut TestScript| {
+        assert!(TestScript::property_default_value(StringName::from("target")).is_none());
+        assert_eq!(
+            script.missing_required_exports().is_empty(),
+            script.target.is_init()
+        );
+
+        if !script.target.is_init() {
+            assert!(script.target.get().is_none());
+            return;
+        }
+
+        if let Some(target) = script.target.get_mut() {
+            target.set_visible(true);
+        }
+    };
+}
+
+#[test]
+fn verify_required_export() {
+    assert!(<OnEditor<Gd<Node3D>> as GodotScriptExport>::REQUIRED);
+    assert!(!<Option<Gd<Node3D>> as GodotScriptExport>::REQUIRED);
+    assert!(!GodotScriptExport::is_assigned(
+        &OnEditor::<Gd<Node3D>>::default()
+    ));
+}
+
+#[test]
+fn verify_script_to_string_prefix() {
+    let _ = |custom: &CustomBaseScript, prefixed: &PrefixedScript| {
+        assert_eq!(
+            GodotScript::to_string(custom),
+            format!("LabeledNode({})", custom.label)
+        );
+        assert_eq!(
+            GodotScript::to_string(prefixed),
+            format!(
+                "<{}#{}>",
+                PrefixedScript::CLASS_NAME,
+                prefixed.base.instance_id()
+            )
+        );
+    };
+}
+
+#[test]
+fn verify_configuration_warnings() {
+    let _ = |mut script: RsRef<CustomBaseScript>| {
+        let empty_label = GString::from("label is empty");
+
+        script.bind_mut().unwrap().label = GString::new();
+
+        let warnings: PackedStringArray = script.call("_get_configuration_warnings", &[]).to();
+
+        assert!(warnings.as_slice().contains(&empty_label));
+
+        script.bind_mut().unwrap().label = GString::from("named");
+
+        let warnings: PackedStringArray = script.call("_get_configuration_warnings", &[]).to();
+
+        assert!(!warnings.as_slice().contains(&empty_label));
+    };
+
+    let _ = |mut script: RsRef<TestScript>| {
+        script.bind_mut().unwrap().target = OnEditor::default();
+
+        let warnings: PackedStringArray = script.call("_get_configuration_warnings", &[]).to();
+
+        assert!(warnings.as_slice().contains(&GString::from(
+            "Required property \"target\" has not been assigned."
+        )));
+    };
+}
+
+#[test]
+fn verify_is_script() {
+    let _ = |node: Gd<Node>| {
+        if node.is_script::<CustomBaseScript>() {
+            assert!(!node.is_script::<TestScript>());
+        }
+    };
+}
+
+#[test]
+fn verify_weak_script_ref() {
+    let _ = |script: RsRef<CustomBaseScript>| {
+        let weak = script.downgrade();
+
+        assert_eq!(weak.instance_id(), script.instance_id());
+        assert!(weak.upgrade().is_some());
+    };
+}
+
+#[test]
+fn verify_vararg_dispatch() {
+    let _ = |mut script: RsRef<TestScript>| {
+        let extra = [1.to_variant(), "two".to_variant()];
+
+        assert_eq!(script.log(1, &[&extra[0], &extra[1]]), 2);
+        assert_eq!(
+            script
+                .try_call("log", &[1.to_variant(), extra[0].clone(), extra[1].clone()])
+                .ok(),
+            Some(2.to_variant())
+        );
+        assert!(matches!(
+            script.try_call("log", &[]),
+            Err(GodotScriptCallError::TooFewArguments(_, 1, 0))
+        ));
+    };
+}
+
+#[test]
+fn verify_script_ref_identity() {
+    let _ = |script: RsRef<CustomBaseScript>| {
+        let mut scripts = HashSet::new();
+
+        scripts.insert(script.clone());
+        scripts.insert(script.clone());
+
+        assert_eq!(scripts.len(), 1);
+        assert!(scripts.contains(&script));
+        assert_eq!(script.instance_id(), script.clone().instance_id());
+    };
+}
+
+#[test]
+fn verify_readonly_export() {
+    let _ = |script: &mut TestScript| {
+        assert!(script.get(StringName::from("spawned_count")).is_some());
+        assert!(!script.set(StringName::from("spawned_count"), 1.to_variant()));
+    };
+}
+
+#[test]
+fn verify_script_ref_cast() {
+    let _ = |script: RsRef<SpatialScript>| {
+        let same = script.clone().try_cast::<SpatialScript>();
+
+        assert_eq!(same.ok(), Some(script.clone()));
+
+        let other = script.try_cast::<TestScript>();
+
+        assert!(matches!(
+            other,
+            Err(GodotScriptCastError::ClassMismatch("TestScript", class)) if class == "SpatialScript"
+        ));
+    };
+}
+
+#[test]
+fn verify_script_ref_array_hint() {
+    let _ = || {
+        let hint_string =
+            <RsRefArray<CustomBaseScript> as GodotScriptExport>::hint_string(None, None);
+
+        assert_eq!(hint_string, "24/34:LabeledNode");
+    };
+}
+
+#[cfg(since_api = "4.4")]
+#[test]
+fn verify_typed_dictionary_hint() {
+    let hint_string =
+        <godot_rust_script::TypedDictionary<GString, i64> as GodotScriptExport>::hint_string(
+            None, None,
+        );
+
+    assert_eq!(hint_string, "4/0:;2/0:");
+}